@@ -0,0 +1,270 @@
+use eframe::egui;
+use egui::Slider;
+use egui_gauge::{
+    AtomicF64, Dial, Easing, Gauge, GaugeCluster, GaugeConfig, GaugeSource, GaugeStyle,
+    LinearGauge, Scale, Smoothing, Spring, Zone,
+};
+use epaint::Color32;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+/// A source of values which wander up and down
+#[derive(Default)]
+struct RandomWalk {
+    value: f64,
+    step: u64,
+}
+
+impl GaugeSource for RandomWalk {
+    fn sample(&mut self) -> f64 {
+        // a cheap pseudo random number, so that the example doesn't need any dependencies
+        self.step = self.step.wrapping_mul(6364136223846793005).wrapping_add(1);
+        let change = (self.step >> 33) as f64 / (1u64 << 31) as f64 - 0.5;
+        self.value = (self.value + change).clamp(0.0, 10.0);
+        self.value
+    }
+
+    fn range_hint(&self) -> Option<std::ops::RangeInclusive<f64>> {
+        Some(0.0..=10.0)
+    }
+}
+
+#[derive(Default)]
+struct FeaturesExample {
+    value: u64,
+    telemetry: Arc<AtomicF64>,
+    random_walk: RandomWalk,
+    setpoint: f64,
+    settings: bool,
+    retained: Option<Gauge<'static>>,
+    config: Option<GaugeConfig>,
+    limits: Vec<Zone>,
+    brightness: f32,
+}
+
+impl FeaturesExample {
+    fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        let example = Self {
+            limits: vec![
+                Zone::new(60..=80, Color32::YELLOW).warning(),
+                Zone::new(80..=100, Color32::RED).critical(),
+            ],
+            brightness: 1.0,
+            ..Self::default()
+        };
+
+        // publish a value from a background thread, like telemetry from a sensor
+        let telemetry = example.telemetry.clone();
+        let ctx = cc.egui_ctx.clone();
+        std::thread::spawn(move || {
+            let start = std::time::Instant::now();
+            loop {
+                let value = (start.elapsed().as_secs_f64().sin() + 1.0) * 50.0;
+                telemetry.store(value, Ordering::Relaxed);
+                ctx.request_repaint();
+                std::thread::sleep(std::time::Duration::from_millis(100));
+            }
+        });
+
+        example
+    }
+}
+
+impl eframe::App for FeaturesExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Features Example");
+            ui.spacing_mut().slider_width = 300.0;
+            ui.add(Slider::new(&mut self.value, 0..=100));
+            ui.add(
+                Gauge::new(self.value, 0..=100, 200.0, Color32::BLUE)
+                    .text("hello")
+                    .rate_of_change(100.0)
+                    .sparkline(30)
+                    .spring(Spring::default())
+                    .zone(
+                        Zone::new(80..=100, Color32::RED)
+                            .critical()
+                            .name("overload"),
+                    )
+                    .zone_text_color(true)
+                    .announce_alarms(true)
+                    .blink(2.0)
+                    .peak_hold(5.0)
+                    .odometer(ui.input(|i| i.time) * 10.0),
+            );
+            ui.add(
+                Gauge::new_mut(&mut self.setpoint, 0.0..=50.0, 200.0, Color32::GREEN)
+                    .text("drag me")
+                    .units("mph")
+                    .show_tooltip(true)
+                    .step_by(0.5)
+                    .default_value(25.0)
+                    .context_menu(true),
+            );
+            ui.add(
+                Gauge::new(self.value + 100, 100..=200, 300.0, Color32::RED)
+                    .text("some text")
+                    .second_value(60, Color32::LIGHT_BLUE)
+                    .legend("value", "limit")
+                    .easing(Easing::EaseInOut),
+            );
+
+            ui.horizontal_wrapped(|ui| {
+                ui.add(
+                    Gauge::new(self.value, 0..=100, 200.0, Color32::BLUE)
+                        .text("half")
+                        .dial(Dial::Half),
+                );
+                ui.add(Gauge::new(self.value, 0..=100, 80.0, Color32::BLUE).text("tiny"));
+                let retained = self.retained.get_or_insert_with(|| {
+                    Gauge::new(0, 0..=100, 200.0, Color32::BLUE)
+                        .text("kept around")
+                        .animated(true)
+                });
+                retained.set_value(self.value);
+                ui.add(retained);
+                self.config
+                    .get_or_insert_with(|| {
+                        Gauge::new(0, 0..=100, 200.0, Color32::BLUE)
+                            .text("configured once")
+                            .dial(Dial::Half)
+                            .into()
+                    })
+                    .show(ui, self.value);
+                ui.add(
+                    Gauge::from_atomic(&*self.telemetry, 0..=100, 200.0, Color32::BLUE)
+                        .text("from a thread")
+                        .on_hover(|ui, output| {
+                            ui.label(format!("{:.1}, published by a thread", output.value));
+                        }),
+                );
+                ui.add(
+                    Gauge::from_source(&mut self.random_walk, 200.0, Color32::BLUE)
+                        .text("from a source")
+                        .smoothing(Smoothing::Ema(0.1))
+                        .statistics(true),
+                );
+                ui.add(
+                    Gauge::new(self.value, 0..=100, 300.0, Color32::BLUE)
+                        .text("wide")
+                        .aspect_ratio(0.5),
+                );
+                ui.add(
+                    Gauge::new(self.value, 0..=100, 300.0, Color32::BLUE)
+                        .text("quarter")
+                        .dial(Dial::Quarter),
+                );
+                let (user, system) = (self.value / 2, self.value / 4);
+                ui.add(
+                    Gauge::new(user + system + 5, 0..=100, 200.0, Color32::GRAY)
+                        .text("cpu")
+                        .units("%")
+                        .segment("user", user, Color32::BLUE)
+                        .segment("system", system, Color32::from_rgb(230, 120, 0))
+                        .segment("iowait", 5, Color32::RED)
+                        .legend("total", ""),
+                );
+            });
+
+            ui.collapsing("Cluster", |ui| {
+                let scale = Scale::new(0..=100)
+                    .divisions(4)
+                    .units("%")
+                    .zone(Zone::new(80..=100, Color32::RED).critical());
+                ui.add(Slider::new(&mut self.brightness, 0.0..=1.0).text("brightness"));
+                let style = GaugeStyle {
+                    brightness: Some(self.brightness),
+                    ..Default::default()
+                };
+                GaugeCluster::new("cluster")
+                    .style(style)
+                    .show(ui, |cluster| {
+                        for name in ["oil", "water", "fuel", "boost"] {
+                            cluster.add(
+                                name,
+                                Gauge::new(self.value, 0..=100, cluster.size(), Color32::BLUE)
+                                    .scale(&scale)
+                                    .link_group("cluster"),
+                            );
+                        }
+                    });
+            });
+
+            ui.collapsing("Dashboard", |ui| {
+                egui::Grid::new("dashboard").show(ui, |ui| {
+                    for row in 0..4u64 {
+                        for column in 0..8 {
+                            let value = (self.value + row * 17 + column * 29) % 101;
+                            ui.add(Gauge::new_compact(value, 0..=100, Color32::BLUE));
+                        }
+                        ui.end_row();
+                    }
+                });
+            });
+
+            ui.collapsing("Right to left", |ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Min), |ui| {
+                    ui.add(Gauge::new(self.value, 0..=100, 200.0, Color32::BLUE).text("מהירות"));
+                    ui.add(LinearGauge::new(self.value, 0..=100, 300.0, Color32::BLUE).text("רמה"));
+                });
+            });
+
+            ui.collapsing("Alarm limits", |ui| {
+                ui.add(
+                    Gauge::new(self.value, 0..=100, 200.0, Color32::BLUE)
+                        .text("drag the limits")
+                        .edit_zones(&mut self.limits),
+                );
+                for zone in &self.limits {
+                    ui.label(format!("{:?}: {:?}", zone.severity, zone.range));
+                }
+            });
+
+            ui.collapsing("Annotations", |ui| {
+                let mut gauge = Gauge::new(self.value, 0..=100, 200.0, Color32::BLUE);
+                let response = ui.add(&mut gauge);
+                let radius = gauge.dial_radius() - gauge.arc_thickness() * 2.0;
+                ui.painter().text(
+                    gauge.screen_pos(response.rect, 50, radius),
+                    egui::Align2::CENTER_CENTER,
+                    "half",
+                    egui::FontId::proportional(12.0),
+                    Color32::GRAY,
+                );
+                if let Some(pos) = response.hover_pos() {
+                    ui.label(format!(
+                        "pointing at {:.0}",
+                        gauge.value_at(response.rect, pos)
+                    ));
+                }
+            });
+
+            ui.collapsing("Fit to width", |ui| {
+                ui.add(
+                    Gauge::new(self.value, 0..=100, 0.0, Color32::BLUE)
+                        .dial(Dial::Half)
+                        .fit_to_width(),
+                );
+            });
+
+            ui.checkbox(&mut self.settings, "Settings");
+            egui::Window::new("Settings")
+                .open(&mut self.settings)
+                .vscroll(true)
+                .show(ctx, |ui| {
+                    ctx.settings_ui(ui);
+                });
+        });
+    }
+}
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "Features Example",
+        native_options,
+        Box::new(|cc| Box::new(FeaturesExample::new(cc))),
+    )
+    .unwrap();
+}
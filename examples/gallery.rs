@@ -0,0 +1,26 @@
+use eframe::egui;
+use egui_gauge::demo::GaugeGallery;
+
+#[derive(Default)]
+struct GalleryExample {
+    gallery: GaugeGallery,
+}
+
+impl eframe::App for GalleryExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Gauge Gallery");
+            self.gallery.ui(ui);
+        });
+    }
+}
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "Gauge Gallery",
+        native_options,
+        Box::new(|_cc| Box::<GalleryExample>::default()),
+    )
+    .unwrap();
+}
@@ -0,0 +1,49 @@
+use eframe::egui;
+use egui_gauge::{Gauge, PlotGauge};
+use egui_plot::{Corner, Line, Plot, PlotPoints};
+
+struct PlotExample {
+    start: f64,
+}
+
+impl eframe::App for PlotExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        let now = ctx.input(|i| i.time) - self.start;
+        let value = |t: f64| 50.0 + 40.0 * (t / 2.0).sin();
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            Plot::new("temperature").show(ui, |plot_ui| {
+                let points: PlotPoints = (0..200)
+                    .map(|i| {
+                        let t = now - 10.0 + i as f64 * 0.05;
+                        [t, value(t)]
+                    })
+                    .collect();
+                plot_ui.line(Line::new(points).name("temperature"));
+
+                let latest =
+                    Gauge::new(value(now), 0.0..=100.0, 120.0, egui::Color32::BLUE).units("°C");
+                plot_ui.add(PlotGauge::in_corner(latest, Corner::RightTop));
+
+                let start = now - 10.0;
+                let first = Gauge::new_compact(value(start), 0.0..=100.0, egui::Color32::GRAY);
+                plot_ui.add(PlotGauge::new(first, [start, value(start)]));
+            });
+        });
+        ctx.request_repaint();
+    }
+}
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "Gauges in a plot",
+        native_options,
+        Box::new(|cc| {
+            Box::new(PlotExample {
+                start: cc.egui_ctx.input(|i| i.time),
+            })
+        }),
+    )
+    .unwrap();
+}
@@ -0,0 +1,102 @@
+use eframe::egui;
+use egui::Slider;
+use egui_gauge::{
+    Altimeter, BatteryGauge, BulletGraph, Clock, Compass, Countdown, Donut, LinearGauge,
+    ProgressRing, Tachometer, Thermometer, VuMeter, WindGauge, Zone,
+};
+use epaint::Color32;
+
+#[derive(Default)]
+struct WidgetsExample {
+    value: u64,
+    deadline: Option<std::time::Instant>,
+}
+
+impl eframe::App for WidgetsExample {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ui.heading("Widgets Example");
+            ui.spacing_mut().slider_width = 300.0;
+            ui.add(Slider::new(&mut self.value, 0..=100));
+
+            ui.horizontal_wrapped(|ui| {
+                ui.add(
+                    LinearGauge::new(self.value, 0..=100, 300.0, Color32::BLUE)
+                        .text("linear")
+                        .zone(Zone::new(80..=100, Color32::RED)),
+                );
+                ui.add(
+                    BulletGraph::new(self.value, 0..=100, 300.0, Color32::BLACK)
+                        .text("revenue")
+                        .band(0..=50, Color32::from_gray(120))
+                        .band(50..=80, Color32::from_gray(170))
+                        .band(80..=100, Color32::from_gray(220))
+                        .target(75),
+                );
+                ui.add(Thermometer::new(self.value, 0..=100, 200.0, Color32::RED).text("°C"));
+                ui.add(
+                    Compass::new(self.value as f64 * 3.6, 200.0, Color32::RED)
+                        .bug(90)
+                        .animated(true),
+                );
+                ui.add(
+                    WindGauge::new(
+                        self.value as f64 * 3.6,
+                        self.value / 4,
+                        40,
+                        200.0,
+                        Color32::BLUE,
+                    )
+                    .gust(self.value / 3)
+                    .units("kn"),
+                );
+                ui.add(BatteryGauge::new(self.value, 120.0, Color32::GREEN).charging(true));
+                ui.add(
+                    VuMeter::new(self.value as f64 * 0.23 - 20.0, 200.0, Color32::RED)
+                        .ballistics(0.01, 0.3),
+                );
+                ui.add(
+                    Tachometer::new(self.value as f64 * 80.0, 8000, 200.0, Color32::RED)
+                        .redline(6500),
+                );
+                ui.add(ProgressRing::new(
+                    self.value as f32 / 100.0,
+                    100.0,
+                    Color32::BLUE,
+                ));
+                ui.add(ProgressRing::new(0.0, 100.0, Color32::BLUE).indeterminate(true));
+                ui.add(Altimeter::new(
+                    self.value as f64 * 123.0,
+                    200.0,
+                    Color32::WHITE,
+                ));
+                ui.add(
+                    Donut::new(150.0)
+                        .segment("value", self.value, Color32::BLUE)
+                        .segment("rest", 100 - self.value, Color32::GRAY)
+                        .legend(true),
+                );
+                let minute = std::time::Duration::from_secs(60);
+                let deadline = *self
+                    .deadline
+                    .get_or_insert_with(|| std::time::Instant::now() + minute);
+                ui.add(Countdown::new(deadline, minute, 150.0, Color32::BLUE).text("left"));
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap_or_default();
+                ui.add(Clock::new(now, 200.0, Color32::RED).digital(true));
+                ctx.request_repaint_after(std::time::Duration::from_secs(1));
+            });
+        });
+    }
+}
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "Widgets Example",
+        native_options,
+        Box::new(|_cc| Box::<WidgetsExample>::default()),
+    )
+    .unwrap();
+}
@@ -0,0 +1,180 @@
+//! A dial with several needles turning at different rates, like an altimeter
+
+use crate::{polar, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// One of the needles of an [`Altimeter`]
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Needle {
+    /// How much the value has to change for the needle to go all the way around the dial
+    pub per_revolution: f64,
+    /// The length of the needle, as a fraction of the radius of the dial
+    pub length: f32,
+    /// The width of the needle, as a fraction of the size of the dial
+    pub width: f32,
+}
+
+impl Needle {
+    /// Create a needle which goes around the dial once every `per_revolution` change in value
+    pub fn new(per_revolution: f64, length: f32, width: f32) -> Self {
+        Self {
+            per_revolution,
+            length,
+            width,
+        }
+    }
+}
+
+/// A dial showing one value with several needles at different scales. Like the altimeter of an
+/// aircraft, the longest needle shows hundreds and the shorter ones thousands and ten thousands,
+/// so each digit of the value can be read off of its own needle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Altimeter {
+    value: f64,
+    size: f32,
+    color: Color32,
+    needles: Vec<Needle>,
+}
+
+impl Altimeter {
+    /// Create a dial showing the given value. The given size is the width and height of the dial.
+    /// The given color is the color of the needles.
+    pub fn new(value: impl emath::Numeric, size: f32, color: Color32) -> Self {
+        Self {
+            value: value.to_f64(),
+            size,
+            color,
+            needles: vec![
+                Needle::new(1_000.0, 0.85, 0.015),
+                Needle::new(10_000.0, 0.55, 0.04),
+                Needle::new(100_000.0, 0.95, 0.008),
+            ],
+        }
+    }
+
+    /// Use the given needles instead of the hundreds, thousands and ten thousands, for example to
+    /// show seconds and minutes elapsed. Needles are drawn in order, so later ones are on top.
+    pub fn needles(mut self, needles: impl IntoIterator<Item = Needle>) -> Self {
+        self.needles = needles.into_iter().collect();
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 * 0.95
+    }
+
+    fn font_size(&self) -> f32 {
+        self.size / 10.0
+    }
+
+    /// The angle on screen (counter-clockwise from the x axis) for the given fraction of the way
+    /// around the dial
+    fn fraction_to_angle(fraction: f64) -> f32 {
+        (90.0 - fraction * 360.0) as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+
+        ui.painter().circle_stroke(
+            center,
+            self.radius(),
+            Stroke {
+                width: self.size / 60.0,
+                color: themed_track_color(ui),
+            },
+        );
+        self.paint_scale(ui, center, text_color);
+        self.write_value(ui, center, text_color);
+        for &needle in &self.needles {
+            self.paint_needle(ui, center, needle);
+        }
+        ui.painter()
+            .circle_filled(center, self.size / 30.0, self.color);
+    }
+
+    fn paint_scale(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        for tick in 0..50 {
+            let angle = Self::fraction_to_angle(tick as f64 / 50.0);
+            let length = if tick % 5 == 0 { 0.1 } else { 0.05 };
+            ui.painter().line_segment(
+                [
+                    polar(center, angle, self.radius()),
+                    polar(center, angle, self.radius() * (1.0 - length)),
+                ],
+                Stroke {
+                    width: 1.0,
+                    color: text_color,
+                },
+            );
+        }
+        for digit in 0..10 {
+            ui.painter().text(
+                polar(
+                    center,
+                    Self::fraction_to_angle(digit as f64 / 10.0),
+                    self.radius() * 0.78,
+                ),
+                Align2::CENTER_CENTER,
+                digit.to_string(),
+                FontId {
+                    size: self.font_size(),
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn write_value(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.35,
+            },
+            Align2::CENTER_CENTER,
+            format!("{:.0}", self.value),
+            FontId {
+                size: self.font_size() * 0.8,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn paint_needle(&self, ui: &mut Ui, center: Pos2, needle: Needle) {
+        let angle = Self::fraction_to_angle(self.value / needle.per_revolution);
+        ui.painter().line_segment(
+            [center, polar(center, angle, self.radius() * needle.length)],
+            Stroke {
+                width: self.size * needle.width,
+                color: self.color,
+            },
+        );
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.value),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Altimeter {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
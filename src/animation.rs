@@ -0,0 +1,158 @@
+//! Helpers for animating the value shown by a gauge
+
+/// The curve followed when an animated gauge moves to a new value
+#[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Easing {
+    /// Move at a constant speed
+    #[default]
+    Linear,
+    /// Start slowly, speed up, then slow down again before reaching the new value
+    EaseInOut,
+    /// Start quickly and slow down before reaching the new value
+    EaseOutCubic,
+    /// Map the fraction of the animation time elapsed (from 0 to 1) to the fraction of the
+    /// distance moved (which should be 0 at the start and 1 at the end). Functions can't be
+    /// serialized, so this is skipped by serde.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    Custom(fn(f32) -> f32),
+}
+
+impl Easing {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Self::Linear => t,
+            Self::EaseInOut => {
+                if t < 0.5 {
+                    4.0 * t * t * t
+                } else {
+                    1.0 - (2.0 - 2.0 * t).powi(3) / 2.0
+                }
+            }
+            Self::EaseOutCubic => 1.0 - (1.0 - t).powi(3),
+            Self::Custom(f) => f(t),
+        }
+    }
+}
+
+/// Custom curves are equal when they are the same function
+impl PartialEq for Easing {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Custom(a), Self::Custom(b)) => *a as usize == *b as usize,
+            _ => std::mem::discriminant(self) == std::mem::discriminant(other),
+        }
+    }
+}
+
+/// An animation from one value to another, started at some point in time
+#[derive(Clone, Copy)]
+pub(crate) struct Animation {
+    from: f64,
+    to: f64,
+    start_time: f64,
+}
+
+impl Animation {
+    pub(crate) fn new(value: f64, time: f64) -> Self {
+        Self {
+            from: value,
+            to: value,
+            start_time: time,
+        }
+    }
+
+    /// The value to show at the given time, restarting the animation from the current position
+    /// whenever the target changes. Also returns whether the animation is still in progress.
+    pub(crate) fn update(
+        &mut self,
+        target: f64,
+        time: f64,
+        duration: f32,
+        easing: Easing,
+    ) -> (f64, bool) {
+        let current = self.value_at(time, duration, easing);
+        if target != self.to {
+            *self = Self {
+                from: current,
+                to: target,
+                start_time: time,
+            };
+        }
+        let in_progress = time - self.start_time < duration as f64;
+        (current, in_progress)
+    }
+
+    fn value_at(&self, time: f64, duration: f32, easing: Easing) -> f64 {
+        if duration <= 0.0 {
+            return self.to;
+        }
+        let t = ((time - self.start_time) as f32 / duration).clamp(0.0, 1.0);
+        self.from + (self.to - self.from) * easing.apply(t) as f64
+    }
+}
+
+/// Parameters for simulating the indicator as a physical needle on a damped spring, which
+/// overshoots slightly and settles on new values like a real analog instrument
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Spring {
+    /// How strongly the needle is pulled towards the value
+    pub stiffness: f32,
+    /// How strongly the needle's movement is resisted. Values below `2.0 * stiffness.sqrt()`
+    /// overshoot the value before settling.
+    pub damping: f32,
+}
+
+impl Default for Spring {
+    fn default() -> Self {
+        Self {
+            stiffness: 100.0,
+            damping: 12.0,
+        }
+    }
+}
+
+/// The longest time step taken when simulating a spring
+const SPRING_STEP: f64 = 1.0 / 240.0;
+
+/// The state of a needle on a spring, with position and velocity as fractions of the range
+#[derive(Clone, Copy)]
+pub(crate) struct SpringState {
+    position: f64,
+    velocity: f64,
+    time: f64,
+}
+
+impl SpringState {
+    pub(crate) fn new(fraction: f64, time: f64) -> Self {
+        Self {
+            position: fraction,
+            velocity: 0.0,
+            time,
+        }
+    }
+
+    /// Advance the simulation to the given time and return the position of the needle, along
+    /// with whether it is still moving.
+    pub(crate) fn update(&mut self, target: f64, time: f64, spring: Spring) -> (f64, bool) {
+        // don't try to catch up on time spent not being shown
+        let mut remaining = (time - self.time).clamp(0.0, 0.1);
+        self.time = time;
+        while remaining > 0.0 {
+            let dt = remaining.min(SPRING_STEP);
+            let acceleration = spring.stiffness as f64 * (target - self.position)
+                - spring.damping as f64 * self.velocity;
+            self.velocity += acceleration * dt;
+            self.position += self.velocity * dt;
+            remaining -= dt;
+        }
+
+        let settled = (target - self.position).abs() < 1e-4 && self.velocity.abs() < 1e-3;
+        if settled {
+            self.position = target;
+            self.velocity = 0.0;
+        }
+        (self.position, !settled)
+    }
+}
@@ -0,0 +1,57 @@
+//! Values which can be shared with other threads, for gauges showing values from the background
+
+use std::sync::atomic::{AtomicI32, AtomicI64, AtomicU32, AtomicU64, Ordering};
+
+/// An `f64` which can be shared between threads, stored as its bits in an [`AtomicU64`]
+#[derive(Default)]
+pub struct AtomicF64(AtomicU64);
+
+impl AtomicF64 {
+    /// Create an atomic holding the given value
+    pub fn new(value: f64) -> Self {
+        Self(AtomicU64::new(value.to_bits()))
+    }
+
+    /// Read the value, like [`AtomicU64::load`]
+    pub fn load(&self, order: Ordering) -> f64 {
+        f64::from_bits(self.0.load(order))
+    }
+
+    /// Replace the value, like [`AtomicU64::store`]
+    pub fn store(&self, value: f64, order: Ordering) {
+        self.0.store(value.to_bits(), order);
+    }
+}
+
+impl std::fmt::Debug for AtomicF64 {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.load(Ordering::Relaxed).fmt(f)
+    }
+}
+
+/// An atomic number which a gauge can read its value from, see
+/// [`Gauge::from_atomic`](crate::Gauge::from_atomic)
+pub trait AtomicValue {
+    /// The current value, as read with relaxed ordering
+    fn load_f64(&self) -> f64;
+}
+
+impl AtomicValue for AtomicF64 {
+    fn load_f64(&self) -> f64 {
+        self.load(Ordering::Relaxed)
+    }
+}
+
+macro_rules! impl_atomic_value {
+    ($($atomic:ty),*) => {
+        $(
+            impl AtomicValue for $atomic {
+                fn load_f64(&self) -> f64 {
+                    self.load(Ordering::Relaxed) as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_value!(AtomicI32, AtomicI64, AtomicU32, AtomicU64);
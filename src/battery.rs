@@ -0,0 +1,167 @@
+//! A gauge which looks like a battery, for showing how charged something is
+
+use crate::zone::{self, Zone};
+use crate::{themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// A battery outline which is filled in proportionally to its charge, with the charge written
+/// inside as a percentage. By default it turns orange when the charge is low and red when it is
+/// nearly empty.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatteryGauge {
+    charge: f64,
+    width: f32,
+    color: Color32,
+    charging: bool,
+    zones: Vec<Zone>,
+}
+
+impl BatteryGauge {
+    /// Create a battery gauge showing the given charge, as a percentage from 0 to 100. The given
+    /// width is the width of the battery, and its height is proportional to it. The given color
+    /// is the color of the charge when it isn't low.
+    pub fn new(charge: impl emath::Numeric, width: f32, color: Color32) -> Self {
+        Self {
+            charge: charge.to_f64().clamp(0.0, 100.0),
+            width,
+            color,
+            charging: false,
+            zones: vec![
+                Zone::new(0..=20, Color32::from_rgb(255, 165, 0)).warning(),
+                Zone::new(0..=10, Color32::RED).critical(),
+            ],
+        }
+    }
+
+    /// Show a lightning bolt on the battery to indicate that it is charging
+    pub fn charging(mut self, charging: bool) -> Self {
+        self.charging = charging;
+        self
+    }
+
+    /// Add a zone to the battery, for example to change what counts as a low charge. When zones
+    /// overlap, the one added last wins, so these take priority over the default low charge zones.
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    fn height(&self) -> f32 {
+        self.width / 2.0
+    }
+
+    fn outline_width(&self) -> f32 {
+        self.width / 40.0
+    }
+
+    /// The main part of the battery, without the terminal sticking out on the right
+    fn body_rect(&self, rect: Rect) -> Rect {
+        let inset = self.outline_width() / 2.0;
+        Rect::from_min_max(
+            Pos2 {
+                x: rect.left() + inset,
+                y: rect.top() + inset,
+            },
+            Pos2 {
+                x: rect.right() - self.width * 0.08,
+                y: rect.bottom() - inset,
+            },
+        )
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let color = zone::zone_at(&self.zones, self.charge).map_or(self.color, |zone| zone.color);
+        let body = self.body_rect(rect);
+
+        self.paint_outline(ui, rect, body);
+        self.paint_charge(ui, body, color);
+
+        let mut text_area = body;
+        if self.charging {
+            let bolt_area = Rect::from_min_size(body.min, egui::vec2(body.height(), body.height()));
+            self.paint_bolt(ui, bolt_area, text_color);
+            text_area.min.x = bolt_area.right();
+        }
+        self.write_charge(ui, text_area, text_color);
+    }
+
+    fn paint_outline(&mut self, ui: &mut Ui, rect: Rect, body: Rect) {
+        let stroke = Stroke {
+            width: self.outline_width(),
+            color: themed_track_color(ui),
+        };
+        ui.painter().rect_stroke(body, self.height() * 0.1, stroke);
+
+        let terminal = Rect::from_min_max(
+            Pos2 {
+                x: body.right(),
+                y: body.center().y - self.height() * 0.2,
+            },
+            Pos2 {
+                x: rect.right(),
+                y: body.center().y + self.height() * 0.2,
+            },
+        );
+        ui.painter()
+            .rect_filled(terminal, self.height() * 0.05, themed_track_color(ui));
+    }
+
+    fn paint_charge(&mut self, ui: &mut Ui, body: Rect, color: Color32) {
+        let mut filled = body.shrink(self.outline_width() * 2.0);
+        filled.max.x = filled.left() + filled.width() * (self.charge / 100.0) as f32;
+        ui.painter()
+            .rect_filled(filled, self.height() * 0.05, color);
+    }
+
+    /// A lightning bolt, made up of two triangles which overlap in the middle
+    fn paint_bolt(&mut self, ui: &mut Ui, area: Rect, color: Color32) {
+        let point = |x: f32, y: f32| area.lerp_inside(egui::vec2(x, y));
+        ui.painter().add(Shape::convex_polygon(
+            vec![point(0.6, 0.1), point(0.5, 0.58), point(0.3, 0.58)],
+            color,
+            Stroke::NONE,
+        ));
+        ui.painter().add(Shape::convex_polygon(
+            vec![point(0.7, 0.42), point(0.4, 0.9), point(0.5, 0.42)],
+            color,
+            Stroke::NONE,
+        ));
+    }
+
+    fn write_charge(&mut self, ui: &mut Ui, area: Rect, text_color: Color32) {
+        ui.painter().text(
+            area.center(),
+            Align2::CENTER_CENTER,
+            format!("{:.0}%", self.charge),
+            FontId {
+                size: self.height() * 0.4,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.width, self.height());
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.charge),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for BatteryGauge {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,259 @@
+//! A bullet graph, for showing a measure against qualitative ranges and a target
+
+use crate::{is_valid_range, ordered_range, scale, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Ui};
+use epaint::{Color32, Pos2, Stroke};
+use std::ops::RangeInclusive;
+
+/// A compact horizontal graph with a bar for a measure drawn over background bands for ranges like
+/// poor, satisfactory and good, and a tick across it for a comparative target
+#[derive(Clone, Debug, PartialEq)]
+pub struct BulletGraph {
+    value: f64,
+    min_value: f64,
+    max_value: f64,
+    width: f32,
+    color: Color32,
+    text: String,
+    bands: Vec<(RangeInclusive<f64>, Color32)>,
+    target: Option<f64>,
+}
+
+impl BulletGraph {
+    /// Create a bullet graph which displays the given value as part of the given range. The given
+    /// width is the width of the graph, and its height is proportional to it. The given color is
+    /// the color of the bar.
+    pub fn new<Num: emath::Numeric>(
+        value: Num,
+        range: RangeInclusive<Num>,
+        width: f32,
+        color: Color32,
+    ) -> Self {
+        let (min_value, max_value) = ordered_range(&range);
+        Self {
+            value: value.to_f64(),
+            min_value,
+            max_value,
+            width,
+            color,
+            text: Default::default(),
+            bands: Vec::new(),
+            target: None,
+        }
+    }
+
+    /// Text to be displayed to the left of the graph
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Shade the given range of the background with the given color. Bands are usually shades of
+    /// gray, darkest for the poorest range. Without any bands the whole background is one band.
+    pub fn band<Num: emath::Numeric>(mut self, range: RangeInclusive<Num>, color: Color32) -> Self {
+        self.bands
+            .push((range.start().to_f64()..=range.end().to_f64(), color));
+        self
+    }
+
+    /// Mark the target to compare the value against with a tick across the bar
+    pub fn target(mut self, target: impl emath::Numeric) -> Self {
+        self.target = Some(target.to_f64());
+        self
+    }
+
+    fn height(&self) -> f32 {
+        self.width / 5.0
+    }
+
+    fn font_size(&self) -> f32 {
+        self.height() * 0.2
+    }
+
+    fn text_width(&self) -> f32 {
+        if self.text.is_empty() {
+            0.0
+        } else {
+            self.width * 0.25
+        }
+    }
+
+    fn graph_rect(&self, rect: Rect) -> Rect {
+        let margin = self.font_size();
+        Rect::from_min_max(
+            Pos2 {
+                x: rect.left() + self.text_width() + margin,
+                y: rect.top() + self.height() * 0.1,
+            },
+            Pos2 {
+                x: rect.right() - margin,
+                y: rect.top() + self.height() * 0.6,
+            },
+        )
+    }
+
+    fn value_to_x(&self, graph: Rect, v: f64) -> f32 {
+        let fraction = ((v - self.min_value) / (self.max_value - self.min_value)).clamp(0.0, 1.0);
+        graph.left() + graph.width() * fraction as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let graph = self.graph_rect(rect);
+        if !is_valid_range(self.min_value, self.max_value) {
+            self.paint_invalid(ui, rect, graph, text_color.gamma_multiply(0.5));
+            return;
+        }
+
+        self.paint_bands(ui, graph);
+        self.paint_bar(ui, graph);
+        if let Some(target) = self.target {
+            self.paint_target(ui, graph, target, text_color);
+        }
+        self.write_values_along_graph(ui, graph, text_color);
+
+        if !self.text.is_empty() {
+            self.write_text(ui, rect, graph, text_color);
+        }
+    }
+
+    /// A greyed out graph with a dash in place of the bar, for ranges which values can't be placed
+    /// on
+    fn paint_invalid(&mut self, ui: &mut Ui, rect: Rect, graph: Rect, text_color: Color32) {
+        ui.painter()
+            .rect_filled(graph, 0.0, themed_track_color(ui).gamma_multiply(0.5));
+        ui.painter().text(
+            graph.center(),
+            Align2::CENTER_CENTER,
+            "—",
+            FontId {
+                size: self.font_size() * 1.2,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        if !self.text.is_empty() {
+            self.write_text(ui, rect, graph, text_color);
+        }
+    }
+
+    fn paint_bands(&mut self, ui: &mut Ui, graph: Rect) {
+        if self.bands.is_empty() {
+            ui.painter()
+                .rect_filled(graph, 0.0, themed_track_color(ui).gamma_multiply(0.5));
+        }
+        for (range, color) in &self.bands {
+            let band = Rect::from_x_y_ranges(
+                self.value_to_x(graph, *range.start())..=self.value_to_x(graph, *range.end()),
+                graph.y_range(),
+            );
+            ui.painter().rect_filled(band, 0.0, *color);
+        }
+    }
+
+    fn paint_bar(&mut self, ui: &mut Ui, graph: Rect) {
+        let bar = Rect::from_x_y_ranges(
+            graph.left()..=self.value_to_x(graph, self.value),
+            graph
+                .shrink2(egui::vec2(0.0, graph.height() / 3.0))
+                .y_range(),
+        );
+        ui.painter().rect_filled(bar, 0.0, self.color);
+    }
+
+    fn paint_target(&mut self, ui: &mut Ui, graph: Rect, target: f64, color: Color32) {
+        let x = self.value_to_x(graph, target);
+        let inset = graph.height() * 0.15;
+        ui.painter().line_segment(
+            [
+                Pos2 {
+                    x,
+                    y: graph.top() + inset,
+                },
+                Pos2 {
+                    x,
+                    y: graph.bottom() - inset,
+                },
+            ],
+            Stroke {
+                width: self.height() * 0.04,
+                color,
+            },
+        );
+    }
+
+    fn write_text(&mut self, ui: &mut Ui, rect: Rect, graph: Rect, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: rect.left() + self.text_width(),
+                y: graph.center().y,
+            },
+            Align2::RIGHT_CENTER,
+            self.text.clone(),
+            FontId {
+                size: self.font_size() * 1.2,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn write_values_along_graph(&mut self, ui: &mut Ui, graph: Rect, text_color: Color32) {
+        let labels = scale::Labels::default();
+        for value in labels.values(self.min_value, self.max_value) {
+            let x = self.value_to_x(graph, value);
+            ui.painter().line_segment(
+                [
+                    Pos2 {
+                        x,
+                        y: graph.bottom(),
+                    },
+                    Pos2 {
+                        x,
+                        y: graph.bottom() + self.height() * 0.08,
+                    },
+                ],
+                Stroke {
+                    width: 1.0,
+                    color: text_color,
+                },
+            );
+            ui.painter().text(
+                Pos2 {
+                    x,
+                    y: graph.bottom() + self.height() * 0.1,
+                },
+                Align2::CENTER_TOP,
+                labels.format(value, self.min_value, self.max_value),
+                FontId {
+                    size: self.font_size(),
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.width, self.height());
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            label: (!self.text.is_empty()).then(|| self.text.clone()),
+            value: Some(self.value),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for BulletGraph {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,203 @@
+//! Collecting the shapes of a gauge, so that it can be painted without a `Ui`
+
+use egui::text::{LayoutJob, TextWrapping};
+use egui::{Align2, Context, FontId, Galley, Rect, Rounding, Shape, Style};
+use epaint::shape_transform::adjust_colors;
+use epaint::{Color32, Pos2, Stroke};
+use std::sync::Arc;
+
+/// Something like a `Painter` which collects shapes instead of painting them, using the fonts of
+/// a `Context` to lay out text
+pub(crate) struct Canvas {
+    ctx: Context,
+    style: Arc<Style>,
+    shapes: Vec<Shape>,
+}
+
+impl Canvas {
+    pub(crate) fn new(ctx: &Context, style: Arc<Style>) -> Self {
+        Self::reusing(ctx, style, Vec::new())
+    }
+
+    /// A canvas collecting its shapes in the given list, which is emptied first so that only its
+    /// capacity is reused
+    pub(crate) fn reusing(ctx: &Context, style: Arc<Style>, mut shapes: Vec<Shape>) -> Self {
+        shapes.clear();
+        Self {
+            ctx: ctx.clone(),
+            style,
+            shapes,
+        }
+    }
+
+    pub(crate) fn ctx(&self) -> &Context {
+        &self.ctx
+    }
+
+    pub(crate) fn style(&self) -> &Style {
+        &self.style
+    }
+
+    /// An empty canvas with the same context and style
+    pub(crate) fn fork(&self) -> Self {
+        Self::new(&self.ctx, self.style.clone())
+    }
+
+    pub(crate) fn shapes(&self) -> &[Shape] {
+        &self.shapes
+    }
+
+    pub(crate) fn into_shapes(self) -> Vec<Shape> {
+        self.shapes
+    }
+
+    pub(crate) fn add(&mut self, shape: impl Into<Shape>) {
+        self.shapes.push(shape.into());
+    }
+
+    pub(crate) fn extend(&mut self, shapes: impl IntoIterator<Item = Shape>) {
+        self.shapes.extend(shapes);
+    }
+
+    /// Flip the shapes collected so far over the vertical line at the given x, moving text to its
+    /// mirrored place without mirroring the letters, so it reads the same way
+    pub(crate) fn mirror(&mut self, x: f32) {
+        for shape in &mut self.shapes {
+            mirror_shape(shape, x);
+        }
+    }
+
+    /// Darken the shapes collected so far by the given factor, from 0 for black to 1 for
+    /// unchanged, keeping how opaque they are
+    pub(crate) fn dim(&mut self, brightness: f32) {
+        let dim = |color: &mut Color32| {
+            // uncolored text is colored in later, and gets dimmed as the fallback color
+            if *color == Color32::PLACEHOLDER {
+                return;
+            }
+            let [r, g, b, a] = color.to_array();
+            let scale = |c: u8| (c as f32 * brightness).round() as u8;
+            *color = Color32::from_rgba_premultiplied(scale(r), scale(g), scale(b), a);
+        };
+        for shape in &mut self.shapes {
+            adjust_colors(shape, &dim);
+        }
+    }
+
+    pub(crate) fn layout(
+        &self,
+        text: String,
+        font_id: FontId,
+        color: Color32,
+        wrap_width: f32,
+    ) -> Arc<Galley> {
+        self.ctx
+            .fonts(|f| f.layout(text, font_id, color, wrap_width))
+    }
+
+    pub(crate) fn layout_no_wrap(
+        &self,
+        text: String,
+        font_id: FontId,
+        color: Color32,
+    ) -> Arc<Galley> {
+        self.ctx.fonts(|f| f.layout_no_wrap(text, font_id, color))
+    }
+
+    /// Lay out a single line of text, cutting it short with "…" where it would be wider than the
+    /// given width
+    pub(crate) fn layout_elided(
+        &self,
+        text: String,
+        font_id: FontId,
+        color: Color32,
+        max_width: f32,
+    ) -> Arc<Galley> {
+        let mut job = LayoutJob::simple_singleline(text, font_id, color);
+        job.wrap = TextWrapping {
+            max_width,
+            max_rows: 1,
+            break_anywhere: true,
+            overflow_character: Some('…'),
+        };
+        self.ctx.fonts(|f| f.layout_job(job))
+    }
+
+    pub(crate) fn galley(&mut self, pos: Pos2, galley: Arc<Galley>, fallback_color: Color32) {
+        self.add(Shape::galley(pos, galley, fallback_color));
+    }
+
+    pub(crate) fn text(
+        &mut self,
+        pos: Pos2,
+        anchor: Align2,
+        text: impl ToString,
+        font_id: FontId,
+        color: Color32,
+    ) {
+        let galley = self.layout_no_wrap(text.to_string(), font_id, color);
+        let rect = anchor.anchor_size(pos, galley.size());
+        self.galley(rect.min, galley, color);
+    }
+
+    pub(crate) fn circle(&mut self, center: Pos2, radius: f32, fill: Color32, stroke: Stroke) {
+        self.add(epaint::CircleShape {
+            center,
+            radius,
+            fill,
+            stroke,
+        });
+    }
+
+    pub(crate) fn circle_stroke(&mut self, center: Pos2, radius: f32, stroke: Stroke) {
+        self.add(Shape::circle_stroke(center, radius, stroke));
+    }
+
+    pub(crate) fn line_segment(&mut self, points: [Pos2; 2], stroke: Stroke) {
+        self.add(Shape::line_segment(points, stroke));
+    }
+
+    pub(crate) fn rect_stroke(
+        &mut self,
+        rect: Rect,
+        rounding: impl Into<Rounding>,
+        stroke: Stroke,
+    ) {
+        self.add(Shape::rect_stroke(rect, rounding, stroke));
+    }
+}
+
+fn mirror_pos(pos: &mut Pos2, x: f32) {
+    pos.x = 2.0 * x - pos.x;
+}
+
+fn mirror_rect(rect: &mut Rect, x: f32) {
+    *rect = Rect::from_x_y_ranges(2.0 * x - rect.max.x..=2.0 * x - rect.min.x, rect.y_range());
+}
+
+fn mirror_shape(shape: &mut Shape, x: f32) {
+    match shape {
+        Shape::Noop | Shape::Callback(_) => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                mirror_shape(shape, x);
+            }
+        }
+        Shape::Circle(circle) => mirror_pos(&mut circle.center, x),
+        Shape::Ellipse(ellipse) => mirror_pos(&mut ellipse.center, x),
+        Shape::LineSegment { points, .. } => points.iter_mut().for_each(|p| mirror_pos(p, x)),
+        Shape::Path(path) => path.points.iter_mut().for_each(|p| mirror_pos(p, x)),
+        Shape::Rect(rect) => mirror_rect(&mut rect.rect, x),
+        Shape::Text(text) => {
+            // keep the same part of the line of text covering the mirrored span
+            let bounds = text.galley.rect;
+            text.pos.x = 2.0 * x - text.pos.x - bounds.min.x - bounds.max.x;
+        }
+        Shape::Mesh(mesh) => mesh
+            .vertices
+            .iter_mut()
+            .for_each(|v| mirror_pos(&mut v.pos, x)),
+        Shape::QuadraticBezier(curve) => curve.points.iter_mut().for_each(|p| mirror_pos(p, x)),
+        Shape::CubicBezier(curve) => curve.points.iter_mut().for_each(|p| mirror_pos(p, x)),
+    }
+}
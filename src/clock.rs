@@ -0,0 +1,198 @@
+//! An analog clock face
+
+use crate::{polar, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Ui};
+use epaint::{Color32, Pos2, Stroke};
+use std::time::Duration;
+
+/// An analog clock with hour, minute and second hands
+#[derive(Clone, Debug, PartialEq)]
+pub struct Clock {
+    seconds: f64,
+    size: f32,
+    color: Color32,
+    second_hand: bool,
+    digital: bool,
+}
+
+impl Clock {
+    /// Create a clock showing the given time of day, as the time since midnight. The given size is
+    /// the width and height of the clock. The given color is the color of the second hand.
+    pub fn new(time_of_day: Duration, size: f32, color: Color32) -> Self {
+        Self {
+            seconds: time_of_day.as_secs_f64() % (24.0 * 60.0 * 60.0),
+            size,
+            color,
+            second_hand: true,
+            digital: false,
+        }
+    }
+
+    /// Whether to show the second hand, which is shown by default
+    pub fn second_hand(mut self, second_hand: bool) -> Self {
+        self.second_hand = second_hand;
+        self
+    }
+
+    /// Also write the time digitally in the middle of the clock
+    pub fn digital(mut self, digital: bool) -> Self {
+        self.digital = digital;
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 * 0.95
+    }
+
+    /// The angle on screen (counter-clockwise from the x axis) for the given fraction of the way
+    /// around the clock face
+    fn fraction_to_angle(fraction: f64) -> f32 {
+        (90.0 - fraction * 360.0) as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+
+        ui.painter().circle_stroke(
+            center,
+            self.radius(),
+            Stroke {
+                width: self.size / 60.0,
+                color: themed_track_color(ui),
+            },
+        );
+        self.paint_ticks(ui, center, text_color);
+        if self.digital {
+            self.write_digital(ui, center, text_color);
+        }
+        self.paint_hands(ui, center, text_color);
+    }
+
+    fn paint_ticks(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        for hour in 0..12 {
+            let angle = Self::fraction_to_angle(hour as f64 / 12.0);
+            let (length, width) = if hour % 3 == 0 {
+                (0.15, self.size / 60.0)
+            } else {
+                (0.08, self.size / 120.0)
+            };
+            ui.painter().line_segment(
+                [
+                    polar(center, angle, self.radius() * 0.95),
+                    polar(center, angle, self.radius() * (0.95 - length)),
+                ],
+                Stroke {
+                    width,
+                    color: text_color,
+                },
+            );
+        }
+    }
+
+    fn paint_hand(
+        &mut self,
+        ui: &mut Ui,
+        center: Pos2,
+        fraction: f64,
+        length: f32,
+        stroke: Stroke,
+    ) {
+        let angle = Self::fraction_to_angle(fraction);
+        ui.painter().line_segment(
+            [
+                polar(center, angle + 180.0, self.radius() * 0.1),
+                polar(center, angle, self.radius() * length),
+            ],
+            stroke,
+        );
+    }
+
+    fn paint_hands(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        let hours = self.seconds / 60.0 / 60.0;
+        self.paint_hand(
+            ui,
+            center,
+            hours % 12.0 / 12.0,
+            0.5,
+            Stroke {
+                width: self.size / 35.0,
+                color: text_color,
+            },
+        );
+        self.paint_hand(
+            ui,
+            center,
+            hours.fract(),
+            0.75,
+            Stroke {
+                width: self.size / 60.0,
+                color: text_color,
+            },
+        );
+        if self.second_hand {
+            // the second hand ticks rather than sweeping
+            self.paint_hand(
+                ui,
+                center,
+                self.seconds.floor() % 60.0 / 60.0,
+                0.85,
+                Stroke {
+                    width: self.size / 150.0,
+                    color: self.color,
+                },
+            );
+        }
+        ui.painter()
+            .circle_filled(center, self.size / 40.0, self.color);
+    }
+
+    fn write_digital(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        let seconds = self.seconds as u64;
+        let time = if self.second_hand {
+            format!(
+                "{:02}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        } else {
+            format!("{:02}:{:02}", seconds / 3600, seconds / 60 % 60)
+        };
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.4,
+            },
+            Align2::CENTER_CENTER,
+            time,
+            FontId {
+                size: self.size / 14.0,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.seconds),
+            ..egui::WidgetInfo::new(egui::WidgetType::Label)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Clock {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
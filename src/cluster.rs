@@ -0,0 +1,126 @@
+//! Laying out many gauges together, like the instrument panel of a dashboard
+
+use crate::{GaugeStyle, GaugeTheme};
+use egui::{Align, Id, Layout, Response, Ui, Widget};
+
+/// A grid of equally sized gauges with captions under them. The number of columns adapts to the
+/// available width, and the gauges grow to fill it.
+///
+/// Gauges are added with [`ClusterUi::add`] inside of [`Self::show`], and should be created with
+/// the size from [`ClusterUi::size`]. With [`Self::style`] they share a style, like dimming
+/// the whole panel together at night.
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaugeCluster {
+    id: Id,
+    min_size: f32,
+    columns: Option<usize>,
+    style: Option<GaugeStyle>,
+}
+
+impl GaugeCluster {
+    /// Create a cluster identified by the given source, which has to be unique within the parent
+    /// `Ui`
+    pub fn new(id_source: impl std::hash::Hash) -> Self {
+        Self {
+            id: Id::new(id_source),
+            min_size: 150.0,
+            columns: None,
+            style: None,
+        }
+    }
+
+    /// The smallest the gauges can get before some of them wrap onto the next row. Defaults to
+    /// 150 points.
+    pub fn min_size(mut self, min_size: f32) -> Self {
+        self.min_size = min_size;
+        self
+    }
+
+    /// Always use the given number of columns, whatever the available width
+    pub fn columns(mut self, columns: usize) -> Self {
+        self.columns = Some(columns.max(1));
+        self
+    }
+
+    /// Give the gauges in the cluster the given style, on top of the installed [`GaugeTheme`].
+    /// Anything the own style of a gauge sets still takes precedence. Like the theme, the track
+    /// and text colors also apply to the other widgets in the cluster.
+    pub fn style(mut self, style: GaugeStyle) -> Self {
+        self.style = Some(style);
+        self
+    }
+
+    /// Lay out the gauges added by the given function
+    pub fn show(self, ui: &mut Ui, add_contents: impl FnOnce(&mut ClusterUi<'_>)) -> Response {
+        let available = ui.available_width();
+        let spacing = ui.spacing().item_spacing.x;
+        let columns = self.columns.unwrap_or_else(|| {
+            (((available + spacing) / (self.min_size + spacing)).floor() as usize).max(1)
+        });
+        let size = ((available - spacing * (columns - 1) as f32) / columns as f32).max(0.0);
+
+        // the gauges pick up the style through the theme for as long as the cluster is shown
+        let theme = GaugeTheme::get(ui.ctx());
+        if let Some(style) = &self.style {
+            GaugeTheme::install(
+                ui.ctx(),
+                GaugeTheme {
+                    gauge: style.clone().or(&theme.gauge),
+                },
+            );
+        }
+        let response = egui::Grid::new(self.id)
+            .show(ui, |ui| {
+                add_contents(&mut ClusterUi {
+                    ui,
+                    size,
+                    columns,
+                    count: 0,
+                });
+            })
+            .response;
+        if self.style.is_some() {
+            GaugeTheme::install(ui.ctx(), theme);
+        }
+        response
+    }
+}
+
+/// Adds gauges to a [`GaugeCluster`]
+pub struct ClusterUi<'u> {
+    ui: &'u mut Ui,
+    size: f32,
+    columns: usize,
+    count: usize,
+}
+
+impl ClusterUi<'_> {
+    /// The size to create the gauges in the cluster with
+    pub fn size(&self) -> f32 {
+        self.size
+    }
+
+    /// Add a gauge to the next cell of the cluster, with the given caption under it
+    pub fn add(&mut self, caption: impl Into<String>, gauge: impl Widget) -> Response {
+        let caption = caption.into();
+        let response = self
+            .ui
+            .allocate_ui_with_layout(
+                egui::vec2(self.size, 0.0),
+                Layout::top_down(Align::Center),
+                |ui| {
+                    let response = ui.add(gauge);
+                    if !caption.is_empty() {
+                        ui.label(caption);
+                    }
+                    response
+                },
+            )
+            .inner;
+        self.count += 1;
+        if self.count % self.columns == 0 {
+            self.ui.end_row();
+        }
+        response
+    }
+}
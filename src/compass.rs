@@ -0,0 +1,230 @@
+//! A full circle dial for showing a heading
+
+use crate::{polar, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Id, Painter, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+const CARDINALS: [&str; 8] = ["N", "NE", "E", "SE", "S", "SW", "W", "NW"];
+
+/// The name of the cardinal or intercardinal direction nearest to the given heading
+pub(crate) fn cardinal(heading: f64) -> &'static str {
+    CARDINALS[(heading.rem_euclid(360.0) / 45.0).round() as usize % CARDINALS.len()]
+}
+
+/// The angle on screen (counter-clockwise from the x axis) for the given heading
+pub(crate) fn heading_to_angle(heading: f64) -> f32 {
+    (90.0 - heading) as f32
+}
+
+/// Ticks every 10 degrees around the inside of a circle of the given radius, longer every 30
+pub(crate) fn paint_ticks(painter: &Painter, center: Pos2, radius: f32, color: Color32) {
+    for heading in (0..360).step_by(10) {
+        let angle = heading_to_angle(heading as f64);
+        let length = if heading % 30 == 0 { 0.1 } else { 0.05 };
+        painter.line_segment(
+            [
+                polar(center, angle, radius),
+                polar(center, angle, radius * (1.0 - length)),
+            ],
+            Stroke { width: 1.0, color },
+        );
+    }
+}
+
+/// The names of the directions around the inside of a circle of the given radius
+pub(crate) fn write_cardinals(
+    painter: &Painter,
+    center: Pos2,
+    radius: f32,
+    font_size: f32,
+    color: Color32,
+) {
+    for (i, cardinal) in CARDINALS.iter().enumerate() {
+        let angle = heading_to_angle(i as f64 * 45.0);
+        // the intercardinal directions are less important
+        let size = if i % 2 == 0 {
+            font_size
+        } else {
+            font_size * 0.7
+        };
+        painter.text(
+            polar(center, angle, radius * 0.78),
+            Align2::CENTER_CENTER,
+            cardinal,
+            FontId {
+                size,
+                family: FontFamily::Monospace,
+            },
+            color,
+        );
+    }
+}
+
+/// The shortest signed distance in degrees to turn from one heading to another
+fn heading_delta(from: f64, to: f64) -> f64 {
+    (to - from + 180.0).rem_euclid(360.0) - 180.0
+}
+
+/// A compass showing a heading in degrees, with 0 being north and increasing clockwise
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compass {
+    heading: f64,
+    size: f32,
+    color: Color32,
+    bug: Option<f64>,
+    animation_time: Option<f32>,
+}
+
+impl Compass {
+    /// Create a compass with its needle pointing at the given heading in degrees. Headings outside
+    /// of 0 to 360 wrap around. The given size is the width and height of the compass. The given
+    /// color is the color of the needle.
+    pub fn new(heading: impl emath::Numeric, size: f32, color: Color32) -> Self {
+        Self {
+            heading: heading.to_f64().rem_euclid(360.0),
+            size,
+            color,
+            bug: None,
+            animation_time: None,
+        }
+    }
+
+    /// Mark a second heading, like the heading to steer towards, on the edge of the dial
+    pub fn bug(mut self, heading: impl emath::Numeric) -> Self {
+        self.bug = Some(heading.to_f64().rem_euclid(360.0));
+        self
+    }
+
+    /// Smoothly turn the needle to new headings, always the short way around
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animation_time = animated.then_some(0.5);
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 * 0.95
+    }
+
+    fn font_size(&self) -> f32 {
+        self.size / 14.0
+    }
+
+    /// The heading to point the needle at, which is unwrapped so that animating it always turns
+    /// the short way
+    fn needle_heading(&self, ui: &Ui, id: Id) -> f64 {
+        let Some(time) = self.animation_time else {
+            return self.heading;
+        };
+        let unwrapped = ui.data_mut(|d| {
+            let unwrapped = d.get_temp_mut_or_insert_with(id, || self.heading);
+            *unwrapped += heading_delta(*unwrapped, self.heading);
+            *unwrapped
+        });
+        ui.ctx()
+            .animate_value_with_time(id.with("animation"), unwrapped as f32, time) as f64
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect, needle_heading: f64) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+
+        ui.painter().circle_stroke(
+            center,
+            self.radius(),
+            Stroke {
+                width: self.size / 60.0,
+                color: themed_track_color(ui),
+            },
+        );
+        paint_ticks(ui.painter(), center, self.radius(), text_color);
+        write_cardinals(
+            ui.painter(),
+            center,
+            self.radius(),
+            self.font_size(),
+            text_color,
+        );
+        if let Some(bug) = self.bug {
+            self.paint_bug(ui, center, bug);
+        }
+        self.paint_needle(ui, center, needle_heading);
+        self.write_heading(ui, center, text_color);
+    }
+
+    fn paint_bug(&mut self, ui: &mut Ui, center: Pos2, bug: f64) {
+        let angle = heading_to_angle(bug);
+        let tip = self.radius() * 0.9;
+        let base = self.radius() * 1.02;
+        ui.painter().add(Shape::convex_polygon(
+            vec![
+                polar(center, angle, tip),
+                polar(center, angle - 4.0, base),
+                polar(center, angle + 4.0, base),
+            ],
+            self.color.gamma_multiply(0.6),
+            Stroke::NONE,
+        ));
+    }
+
+    fn paint_needle(&mut self, ui: &mut Ui, center: Pos2, heading: f64) {
+        let angle = heading_to_angle(heading);
+        let half_width = self.size / 40.0;
+        let side = polar(center, angle + 90.0, half_width);
+        let other_side = polar(center, angle - 90.0, half_width);
+        ui.painter().add(Shape::convex_polygon(
+            vec![polar(center, angle, self.radius() * 0.65), side, other_side],
+            self.color,
+            Stroke::NONE,
+        ));
+        ui.painter().add(Shape::convex_polygon(
+            vec![
+                polar(center, angle + 180.0, self.radius() * 0.4),
+                other_side,
+                side,
+            ],
+            themed_track_color(ui),
+            Stroke::NONE,
+        ));
+        ui.painter()
+            .circle_filled(center, half_width * 1.5, self.color);
+    }
+
+    fn write_heading(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.45,
+            },
+            Align2::CENTER_CENTER,
+            format!("{:.0}°", self.heading),
+            FontId {
+                size: self.font_size(),
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.heading),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        let needle_heading = self.needle_heading(ui, response.id);
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect, needle_heading);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Compass {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,33 @@
+//! Gauges which are configured once and shown with a new value every frame
+
+use crate::Gauge;
+use egui::{Response, Ui};
+
+/// Everything about a [`Gauge`] except its value: the range, size, style, zones, label formats and
+/// so on. Configure a gauge once with the builder methods of [`Gauge`], turn it into a config
+/// with `GaugeConfig::from`, keep it in the state of the app, and show it with
+/// [`Self::show`] every frame, rather than repeating the builder calls.
+///
+/// Only the value changes from frame to frame. To change anything else, build a new config.
+/// Bindings can't outlive a frame, so any binding of the gauge and any zones being edited with
+/// [`Gauge::edit_zones`] are dropped.
+#[derive(Debug)]
+pub struct GaugeConfig {
+    gauge: Gauge<'static>,
+}
+
+impl From<Gauge<'static>> for GaugeConfig {
+    fn from(mut gauge: Gauge<'static>) -> Self {
+        gauge.binding = None;
+        gauge.zone_edit = None;
+        Self { gauge }
+    }
+}
+
+impl GaugeConfig {
+    /// Show a gauge with this configuration and the given value
+    pub fn show(&mut self, ui: &mut Ui, value: impl emath::Numeric) -> Response {
+        self.gauge.set_value(value);
+        ui.add(&mut self.gauge)
+    }
+}
@@ -0,0 +1,188 @@
+//! A gauge which counts down to a deadline
+
+use crate::{arc_points, themed_text_color, themed_track_color, Dial};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Stroke};
+use std::time::{Duration, Instant};
+
+/// A gauge showing the time left until a deadline, which empties as time passes and writes the
+/// remaining time in the middle. It repaints itself until the deadline is reached.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Countdown {
+    deadline: Instant,
+    total: Duration,
+    size: f32,
+    color: Color32,
+    fill: bool,
+    text: String,
+}
+
+impl Countdown {
+    /// Create a countdown to the given deadline, where the arc is full when the given total time
+    /// is left. The given size is the width and height of the gauge. The given color is the color
+    /// of the arc.
+    pub fn new(deadline: Instant, total: Duration, size: f32, color: Color32) -> Self {
+        Self {
+            deadline,
+            total,
+            size,
+            color,
+            fill: false,
+            text: Default::default(),
+        }
+    }
+
+    /// Fill the arc up as time passes instead of emptying it
+    pub fn fill(mut self, fill: bool) -> Self {
+        self.fill = fill;
+        self
+    }
+
+    /// Text to be displayed under the remaining time
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    fn thickness(&self) -> f32 {
+        self.size / 15.0
+    }
+
+    fn radius(&self) -> f32 {
+        (self.size - self.thickness()) / 2.0
+    }
+
+    fn remaining(&self) -> Duration {
+        self.deadline.saturating_duration_since(Instant::now())
+    }
+
+    /// How much of the arc is colored in
+    fn fraction(&self, remaining: Duration) -> f32 {
+        let left = if self.total.is_zero() {
+            0.0
+        } else {
+            (remaining.as_secs_f32() / self.total.as_secs_f32()).min(1.0)
+        };
+        if self.fill {
+            1.0 - left
+        } else {
+            left
+        }
+    }
+
+    /// How long until the gauge looks any different: the written time changes every second, and
+    /// the end of the arc moves by a point every so often
+    fn next_change(&self, remaining: Duration) -> Duration {
+        let to_next_second = Duration::from_nanos(u64::from(remaining.subsec_nanos()));
+        let to_next_second = if to_next_second.is_zero() {
+            Duration::from_secs(1)
+        } else {
+            to_next_second
+        };
+        let arc_length = self.radius().max(1.0) * std::f32::consts::PI * 1.5;
+        let per_point = self.total.div_f32(arc_length);
+        // with no total time the arc stays empty
+        let next = if per_point.is_zero() {
+            to_next_second
+        } else {
+            to_next_second.min(per_point)
+        };
+        next.min(remaining)
+    }
+
+    fn format_remaining(remaining: Duration) -> String {
+        // round up, so that 0:00 is only shown once the time is up
+        let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        if seconds >= 60 * 60 {
+            format!(
+                "{}:{:02}:{:02}",
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            )
+        } else {
+            format!("{}:{:02}", seconds / 60, seconds % 60)
+        }
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect, remaining: Duration) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+        let dial = Dial::ThreeQuarter;
+        let start = dial.start_angle() as f32;
+        let end = dial.end_angle() as f32;
+
+        ui.painter().add(Shape::line(
+            arc_points(center, self.radius(), start, end),
+            Stroke {
+                width: self.thickness(),
+                color: themed_track_color(ui),
+            },
+        ));
+        let fraction = self.fraction(remaining);
+        if fraction > 0.0 {
+            ui.painter().add(Shape::line(
+                arc_points(
+                    center,
+                    self.radius(),
+                    start,
+                    start - (start - end) * fraction,
+                ),
+                Stroke {
+                    width: self.thickness(),
+                    color: self.color,
+                },
+            ));
+        }
+
+        ui.painter().text(
+            center,
+            Align2::CENTER_CENTER,
+            Self::format_remaining(remaining),
+            FontId {
+                size: self.size / 6.0,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        if !self.text.is_empty() {
+            ui.painter().text(
+                egui::pos2(center.x, center.y + self.size / 5.0),
+                Align2::CENTER_CENTER,
+                self.text.clone(),
+                FontId {
+                    size: self.size / 12.0,
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        let remaining = self.remaining();
+
+        response.widget_info(|| egui::WidgetInfo {
+            label: (!self.text.is_empty()).then(|| self.text.clone()),
+            value: Some(remaining.as_secs_f64()),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect, remaining);
+        }
+        if !remaining.is_zero() {
+            ui.ctx().request_repaint_after(self.next_change(remaining));
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Countdown {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,196 @@
+//! A gallery of all the widgets in this crate, for trying out their options
+
+use crate::{
+    Altimeter, BatteryGauge, BulletGraph, Compass, Dial, Donut, Easing, Gauge, LinearGauge,
+    ProgressRing, Smoothing, Spring, Tachometer, Thermometer, VuMeter, Zone,
+};
+use egui::{ComboBox, ScrollArea, Slider, Ui};
+use epaint::Color32;
+
+/// A panel showing every widget of this crate, with controls for the value and the options of
+/// the gauge, like the demos of `egui_demo_lib`. Keep it around between frames and call
+/// [`Self::ui`] every frame.
+pub struct GaugeGallery {
+    value: f64,
+    size: f32,
+    color: Color32,
+    dial: Dial,
+    text: String,
+    units: String,
+    zones: bool,
+    rate_of_change: bool,
+    sparkline: bool,
+    trail: bool,
+    motion: Motion,
+    smoothing: bool,
+    compact: bool,
+    interactive: bool,
+    debug: bool,
+}
+
+/// How the needle of the gauge gets to a new value
+#[derive(Clone, Copy, PartialEq)]
+enum Motion {
+    Jump,
+    Animate,
+    Spring,
+}
+
+impl Default for GaugeGallery {
+    fn default() -> Self {
+        Self {
+            value: 40.0,
+            size: 200.0,
+            color: Color32::BLUE,
+            dial: Dial::default(),
+            text: "speed".into(),
+            units: "mph".into(),
+            zones: true,
+            rate_of_change: false,
+            sparkline: false,
+            trail: false,
+            motion: Motion::Jump,
+            smoothing: false,
+            compact: false,
+            interactive: false,
+            debug: false,
+        }
+    }
+}
+
+impl GaugeGallery {
+    /// Show the controls and the gallery
+    pub fn ui(&mut self, ui: &mut Ui) {
+        ui.horizontal_top(|ui| {
+            ui.vertical(|ui| {
+                ui.set_max_width(250.0);
+                self.controls_ui(ui);
+            });
+            ui.separator();
+            ScrollArea::vertical().show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| self.gallery_ui(ui));
+            });
+        });
+    }
+
+    fn controls_ui(&mut self, ui: &mut Ui) {
+        egui::Grid::new("gauge_gallery_controls")
+            .num_columns(2)
+            .show(ui, |ui| {
+                ui.label("Value");
+                ui.add(Slider::new(&mut self.value, 0.0..=100.0));
+                ui.end_row();
+
+                ui.label("Size");
+                ui.add(Slider::new(&mut self.size, 50.0..=400.0));
+                ui.end_row();
+
+                ui.label("Color");
+                ui.color_edit_button_srgba(&mut self.color);
+                ui.end_row();
+
+                ui.label("Dial");
+                ComboBox::from_id_source("gauge_gallery_dial")
+                    .selected_text(match self.dial {
+                        Dial::ThreeQuarter => "Three quarter",
+                        Dial::Half => "Half",
+                        Dial::Quarter => "Quarter",
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(&mut self.dial, Dial::ThreeQuarter, "Three quarter");
+                        ui.selectable_value(&mut self.dial, Dial::Half, "Half");
+                        ui.selectable_value(&mut self.dial, Dial::Quarter, "Quarter");
+                    });
+                ui.end_row();
+
+                ui.label("Text");
+                ui.text_edit_singleline(&mut self.text);
+                ui.end_row();
+
+                ui.label("Units");
+                ui.text_edit_singleline(&mut self.units);
+                ui.end_row();
+
+                ui.label("Motion");
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.motion, Motion::Jump, "Jump");
+                    ui.selectable_value(&mut self.motion, Motion::Animate, "Animate");
+                    ui.selectable_value(&mut self.motion, Motion::Spring, "Spring");
+                });
+                ui.end_row();
+            });
+
+        ui.checkbox(&mut self.zones, "Warning and critical zones");
+        ui.checkbox(&mut self.rate_of_change, "Rate of change needle");
+        ui.checkbox(&mut self.sparkline, "Sparkline");
+        ui.checkbox(&mut self.trail, "Trail");
+        ui.checkbox(&mut self.smoothing, "Smoothing");
+        ui.checkbox(&mut self.compact, "Compact");
+        ui.checkbox(&mut self.interactive, "Drag to change the value");
+        ui.checkbox(&mut self.debug, "Debug overlay");
+    }
+
+    fn gauge(&mut self) -> Gauge<'_> {
+        let mut gauge = if self.interactive {
+            Gauge::new_mut(&mut self.value, 0.0..=100.0, self.size, self.color)
+        } else {
+            Gauge::new(self.value, 0.0..=100.0, self.size, self.color)
+        };
+        gauge = gauge
+            .text(self.text.clone())
+            .units(self.units.clone())
+            .dial(self.dial)
+            .compact(self.compact)
+            .debug(self.debug)
+            .show_tooltip(true);
+        if self.zones {
+            gauge = gauge
+                .zone(Zone::new(70.0..=90.0, Color32::from_rgb(255, 165, 0)).warning())
+                .zone(Zone::new(90.0..=100.0, Color32::RED).critical());
+        }
+        if self.rate_of_change {
+            gauge = gauge.rate_of_change(100.0);
+        }
+        if self.sparkline {
+            gauge = gauge.sparkline(30);
+        }
+        if self.trail {
+            gauge = gauge.trail(5);
+        }
+        if self.smoothing {
+            gauge = gauge.smoothing(Smoothing::Ema(0.1));
+        }
+        match self.motion {
+            Motion::Jump => gauge,
+            Motion::Animate => gauge.easing(Easing::EaseInOut),
+            Motion::Spring => gauge.spring(Spring::default()),
+        }
+    }
+
+    fn gallery_ui(&mut self, ui: &mut Ui) {
+        let (value, size, color) = (self.value, self.size, self.color);
+        ui.add(self.gauge());
+        ui.add(LinearGauge::new(value, 0.0..=100.0, size * 1.5, color).text(self.text.clone()));
+        ui.add(
+            BulletGraph::new(value, 0.0..=100.0, size * 1.5, color)
+                .text(self.text.clone())
+                .band(0..=50, Color32::from_gray(120))
+                .band(50..=80, Color32::from_gray(170))
+                .band(80..=100, Color32::from_gray(220))
+                .target(75),
+        );
+        ui.add(Thermometer::new(value, 0.0..=100.0, size, color).text(self.units.clone()));
+        ui.add(Compass::new(value * 3.6, size, color).animated(true));
+        ui.add(BatteryGauge::new(value, size * 0.6, color));
+        ui.add(VuMeter::new(value * 0.23 - 20.0, size, color));
+        ui.add(Tachometer::new(value * 80.0, 8000, size, color).redline(6500));
+        ui.add(ProgressRing::new(value as f32 / 100.0, size / 2.0, color));
+        ui.add(Altimeter::new(value * 123.0, size, color));
+        ui.add(
+            Donut::new(size * 0.75)
+                .segment("value", value, color)
+                .segment("rest", 100.0 - value, Color32::GRAY)
+                .legend(true),
+        );
+    }
+}
@@ -0,0 +1,49 @@
+//! The shapes of dial a gauge can be drawn with
+
+/// How much of a circle the arc of a [`Gauge`](crate::Gauge) covers
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Dial {
+    /// Three quarters of a circle, leaving a gap at the bottom
+    #[default]
+    ThreeQuarter,
+    /// The top half of a circle. The gauge has a flat bottom and only allocates about half the
+    /// height, so it packs well at the top of a panel.
+    Half,
+    /// A quarter of a circle from the left to the top, centered on the bottom right corner. The
+    /// gauge allocates about a quarter of the area, so it fits into the corner of a panel.
+    Quarter,
+}
+
+impl Dial {
+    /// The angle where the arc starts, which is where the minimum value is
+    pub(crate) fn start_angle(self) -> i32 {
+        match self {
+            Self::ThreeQuarter => 225,
+            Self::Half | Self::Quarter => 180,
+        }
+    }
+
+    /// The angle where the arc ends, which is where the maximum value is
+    pub(crate) fn end_angle(self) -> i32 {
+        match self {
+            Self::ThreeQuarter => -45,
+            Self::Half => 0,
+            Self::Quarter => 90,
+        }
+    }
+
+    /// How many degrees the arc covers
+    pub(crate) fn sweep(self) -> i32 {
+        self.start_angle() - self.end_angle()
+    }
+
+    /// How big the value and text in the middle are compared to a full gauge, to fit the space
+    /// inside the arc
+    pub(crate) fn content_scale(self) -> f32 {
+        match self {
+            Self::ThreeQuarter | Self::Half => 1.0,
+            Self::Quarter => 0.6,
+        }
+    }
+}
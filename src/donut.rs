@@ -0,0 +1,168 @@
+//! A ring divided into colored segments, like a pie chart
+
+use crate::{arc_points, scale, themed_text_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// A ring divided into segments proportionally to their values, starting at the top and going
+/// clockwise. The total is written in the middle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Donut {
+    segments: Vec<(String, f64, Color32)>,
+    size: f32,
+    legend: bool,
+}
+
+impl Donut {
+    /// Create an empty donut. The given size is the width and height of the ring.
+    pub fn new(size: f32) -> Self {
+        Self {
+            segments: Vec::new(),
+            size,
+            legend: false,
+        }
+    }
+
+    /// Add a segment with the given label, value and color. Negative values are treated as 0.
+    pub fn segment(
+        mut self,
+        label: impl Into<String>,
+        value: impl emath::Numeric,
+        color: Color32,
+    ) -> Self {
+        self.segments
+            .push((label.into(), value.to_f64().max(0.0), color));
+        self
+    }
+
+    /// Show the label, color and share of each segment next to the ring
+    pub fn legend(mut self, legend: bool) -> Self {
+        self.legend = legend;
+        self
+    }
+
+    fn thickness(&self) -> f32 {
+        self.size / 6.0
+    }
+
+    fn radius(&self) -> f32 {
+        (self.size - self.thickness()) / 2.0
+    }
+
+    fn font_size(&self) -> f32 {
+        self.size / 12.0
+    }
+
+    fn total(&self) -> f64 {
+        self.segments.iter().map(|(_, value, _)| value).sum()
+    }
+
+    fn legend_width(&self) -> f32 {
+        self.size
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let ring = Rect::from_min_size(rect.min, egui::vec2(self.size, self.size));
+        let center = ring.center();
+        let total = self.total();
+
+        if total > 0.0 {
+            let mut start = 90.0;
+            for (_, value, color) in &self.segments {
+                let end = start - (value / total * 360.0) as f32;
+                self.paint_segment(ui, center, start, end, *color);
+                start = end;
+            }
+        }
+
+        ui.painter().text(
+            center,
+            Align2::CENTER_CENTER,
+            // as the top of a gauge's scale from zero to the total would be labelled
+            scale::Labels::default().format(total, 0.0, total),
+            FontId {
+                size: self.font_size() * 1.5,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+
+        if self.legend {
+            self.paint_legend(ui, ring.right_top(), total, text_color);
+        }
+    }
+
+    fn paint_segment(&self, ui: &mut Ui, center: Pos2, start: f32, end: f32, color: Color32) {
+        if start - end <= 0.0 {
+            return;
+        }
+        ui.painter().add(Shape::line(
+            arc_points(center, self.radius(), start, end),
+            Stroke {
+                width: self.thickness(),
+                color,
+            },
+        ));
+    }
+
+    fn paint_legend(&self, ui: &mut Ui, top_left: Pos2, total: f64, text_color: Color32) {
+        let line_height = self.font_size() * 1.5;
+        for (i, (label, value, color)) in self.segments.iter().enumerate() {
+            let y = top_left.y + line_height * (i as f32 + 0.5);
+            let swatch = Rect::from_center_size(
+                Pos2 {
+                    x: top_left.x + line_height,
+                    y,
+                },
+                egui::vec2(self.font_size(), self.font_size()),
+            );
+            ui.painter().rect_filled(swatch, 2.0, *color);
+            let share = if total > 0.0 {
+                value / total * 100.0
+            } else {
+                0.0
+            };
+            ui.painter().text(
+                Pos2 {
+                    x: swatch.right() + self.font_size() / 2.0,
+                    y,
+                },
+                Align2::LEFT_CENTER,
+                format!("{label} ({share:.0}%)"),
+                FontId {
+                    size: self.font_size(),
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let width = if self.legend {
+            self.size + self.legend_width()
+        } else {
+            self.size
+        };
+        let desired_size = egui::vec2(width, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.total()),
+            ..egui::WidgetInfo::new(egui::WidgetType::Label)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Donut {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,56 @@
+//! Filling in the frames between timestamped samples of a value
+
+use std::time::Instant;
+
+/// How a gauge given timestamped samples with [`Gauge::sampled_at`](crate::Gauge::sampled_at)
+/// moves its indicator between them, so that a value which is only updated a few times a second
+/// still moves smoothly
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Interpolation {
+    /// Move from the previous sample to the latest one over the time between them. The
+    /// indicator is always on a line between two real samples, at the cost of trailing one
+    /// update behind.
+    #[default]
+    Linear,
+    /// Carry on along the line through the last two samples, for at most the time between them.
+    /// The indicator keeps up with the value, but overshoots when the value turns around.
+    Extrapolate,
+}
+
+/// The last two samples, kept between frames
+#[derive(Clone, Copy, Default)]
+pub(crate) struct InterpolationState {
+    previous: Option<(Instant, f64)>,
+    latest: Option<(Instant, f64)>,
+}
+
+impl InterpolationState {
+    /// The value to show now for the given latest sample, along with whether it is still moving
+    pub(crate) fn update(
+        &mut self,
+        value: f64,
+        time: Instant,
+        now: Instant,
+        interpolation: Interpolation,
+    ) -> (f64, bool) {
+        if self.latest.map(|(latest, _)| latest) != Some(time) {
+            self.previous = self.latest;
+            self.latest = Some((time, value));
+        }
+        let (Some((t0, v0)), Some((t1, v1))) = (self.previous, self.latest) else {
+            return (value, false);
+        };
+        let interval = t1.saturating_duration_since(t0).as_secs_f64();
+        if interval <= 0.0 {
+            return (v1, false);
+        }
+        let elapsed = now.saturating_duration_since(t1).as_secs_f64();
+        let fraction = (elapsed / interval).min(1.0);
+        let shown = match interpolation {
+            Interpolation::Linear => v0 + (v1 - v0) * fraction,
+            Interpolation::Extrapolate => v1 + (v1 - v0) * fraction,
+        };
+        (shown, fraction < 1.0)
+    }
+}
@@ -1,21 +1,483 @@
-///! This crate contains a gauge UI element for use with `egui`
-///! This gauge displays a numeric value in a manner that resembles a speedometer on a car
-///
-use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
-use epaint::{Color32, PathShape, Pos2, Stroke};
-use std::f32::consts::PI;
+//! This crate contains a gauge UI element for use with `egui`
+//! This gauge displays a numeric value in a manner that resembles a speedometer on a car
+
+mod altimeter;
+mod animation;
+mod atomic;
+mod battery;
+mod bullet;
+mod canvas;
+mod clock;
+mod cluster;
+mod compass;
+mod config;
+mod countdown;
+#[cfg(feature = "demo")]
+pub mod demo;
+mod dial;
+mod donut;
+mod interpolation;
+mod linear;
+mod marker;
+mod overflow;
+#[cfg(feature = "egui_plot")]
+mod plot;
+#[cfg(feature = "render")]
+mod render;
+mod ring;
+mod scale;
+mod smoothing;
+mod source;
+mod state;
+mod style;
+#[cfg(feature = "svg")]
+mod svg;
+mod tachometer;
+mod thermometer;
+mod vu;
+#[cfg(feature = "watch")]
+pub mod watch;
+mod wind;
+mod zone;
+
+pub use altimeter::{Altimeter, Needle};
+pub use animation::{Easing, Spring};
+pub use atomic::{AtomicF64, AtomicValue};
+pub use battery::BatteryGauge;
+pub use bullet::BulletGraph;
+pub use clock::Clock;
+pub use cluster::{ClusterUi, GaugeCluster};
+pub use compass::Compass;
+pub use config::GaugeConfig;
+pub use countdown::Countdown;
+pub use dial::Dial;
+pub use donut::Donut;
+pub use interpolation::Interpolation;
+pub use linear::LinearGauge;
+pub use marker::MarkerStyle;
+pub use overflow::OverflowBehavior;
+#[cfg(feature = "egui_plot")]
+pub use plot::PlotGauge;
+pub use ring::ProgressRing;
+pub use scale::Scale;
+pub use smoothing::Smoothing;
+pub use source::GaugeSource;
+pub use style::{GaugeStyle, GaugeTheme, Palette};
+pub use tachometer::Tachometer;
+pub use thermometer::Thermometer;
+pub use vu::VuMeter;
+pub use wind::WindGauge;
+pub use zone::{Severity, Zone};
+
+use animation::{Animation, SpringState};
+use canvas::Canvas;
+use egui::{
+    Align2, Context, EventFilter, FontFamily, FontId, Galley, Id, Key, Painter, Rect, Response,
+    Sense, Shape, Ui, Vec2, Visuals,
+};
+use epaint::{Color32, CubicBezierShape, Pos2, Stroke};
+use state::{GaugeState, Keyed, LinkedHover, PersistedState, ShapeCache, Statistics, RATE_WINDOW};
+use std::collections::VecDeque;
+use std::f32::consts::{FRAC_1_SQRT_2, PI};
 use std::ops::RangeInclusive;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How much holding shift scales down the drag sensitivity
+const FINE_ADJUST: f64 = 0.1;
+
+/// How long (in seconds) the center value stays highlighted after a large change
+const FLASH_DURATION: f64 = 0.5;
+
+/// How often gauges in the low overhead mode repaint while they are moving
+const LOW_OVERHEAD_FRAME_TIME: Duration = Duration::from_millis(50);
+
+/// How many times coarser arcs are in the low overhead mode
+const LOW_OVERHEAD_COARSENESS: f32 = 4.0;
+
+/// Gauges smaller than this use the compact layout unless told otherwise
+const COMPACT_SIZE: f32 = 100.0;
+
+/// The size of gauges made with [`Gauge::new_compact`]
+const DENSE_SIZE: f32 = 72.0;
+
+/// The smallest font used for the scale labels of a compact gauge
+const MIN_LABEL_FONT_SIZE: f32 = 8.0;
+
+/// How a gauge sizes itself to the space it is given
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Fit {
+    Width,
+    AvailableSize,
+}
+
+/// The color of the part of the track which isn't filled in by the value, which is the same as
+/// the rail of a slider
+fn track_color(visuals: &Visuals) -> Color32 {
+    visuals.widgets.inactive.bg_fill
+}
+
+/// The track color of the [`GaugeTheme`] installed in the context, falling back to the default one,
+/// for the widgets which don't have a style of their own
+fn themed_track_color(ui: &Ui) -> Color32 {
+    GaugeTheme::get(ui.ctx())
+        .gauge
+        .track_color
+        .unwrap_or_else(|| track_color(ui.visuals()))
+}
+
+/// The text color of the [`GaugeTheme`] installed in the context, falling back to the text color
+/// of the egui style, for the widgets which don't have a style of their own
+fn themed_text_color(ui: &Ui) -> Color32 {
+    GaugeTheme::get(ui.ctx())
+        .gauge
+        .text_color
+        .unwrap_or_else(|| ui.style().noninteractive().text_color())
+}
+
+/// The point at the given angle (in degrees, counter-clockwise from the x axis) and distance from
+/// the center
+fn polar(center: Pos2, angle: f32, radius: f32) -> Pos2 {
+    let angle = angle.to_radians();
+    Pos2 {
+        x: center.x + angle.cos() * radius,
+        y: center.y - angle.sin() * radius,
+    }
+}
+
+/// How far (in points, or pixels where known) an arc may stray from its straight segments
+const ARC_TOLERANCE: f32 = 0.1;
+
+/// The largest step in degrees between points along an arc of the given radius for the arc to
+/// look smooth, so that small arcs get few points and large arcs many
+fn arc_step(radius: f32) -> f32 {
+    let max_angle = 2.0 * (1.0 - ARC_TOLERANCE / radius.max(ARC_TOLERANCE)).acos();
+    max_angle.to_degrees().clamp(0.25, 10.0)
+}
+
+/// Points along an arc from one angle to another (in degrees, counter-clockwise from the x axis),
+/// spaced by [`arc_step`] but always ending exactly on the given angles
+fn arc_points(center: Pos2, radius: f32, from: f32, to: f32) -> Vec<Pos2> {
+    let steps = ((from - to).abs() / arc_step(radius)).ceil().max(1.0) as usize;
+    (0..=steps)
+        .map(|i| polar(center, from + (to - from) * i as f32 / steps as f32, radius))
+        .collect()
+}
+
+/// The widest angle in degrees covered by one Bézier curve in [`bezier_arc_points`]. A cubic
+/// curve over 45° strays from the circle by less than a millionth of its radius.
+const BEZIER_ARC_SPAN: f32 = 45.0;
+
+/// Points along an elliptical arc from one angle to another (in degrees, counter-clockwise from
+/// the x axis), found by approximating the arc with cubic Bézier curves and letting epaint flatten
+/// them to within the given tolerance. The vertical radius is `radius * aspect_ratio`.
+fn bezier_arc_points(
+    center: Pos2,
+    radius: f32,
+    aspect_ratio: f32,
+    from: f32,
+    to: f32,
+    tolerance: f32,
+) -> Vec<Pos2> {
+    let segments = ((to - from).abs() / BEZIER_ARC_SPAN).ceil().max(1.0) as usize;
+    let span = ((to - from) / segments as f32).to_radians();
+    // the distance from each end of a curve to its control point, as a fraction of the radius
+    let handle = 4.0 / 3.0 * (span / 4.0).tan();
+    let point = |angle: f32, along: f32| Pos2 {
+        x: center.x + (angle.cos() - angle.sin() * along) * radius,
+        y: center.y - (angle.sin() + angle.cos() * along) * radius * aspect_ratio,
+    };
+
+    let mut points = vec![point(from.to_radians(), 0.0)];
+    for i in 0..segments {
+        let start = from.to_radians() + span * i as f32;
+        let end = start + span;
+        let curve = CubicBezierShape {
+            points: [
+                point(start, 0.0),
+                point(start, handle),
+                point(end, -handle),
+                point(end, 0.0),
+            ],
+            closed: false,
+            fill: Color32::TRANSPARENT,
+            stroke: Stroke::NONE,
+        };
+        // written straight into the points, without a list of points for every curve
+        curve.for_each_flattened_with_t(tolerance, &mut |point, _| points.push(point));
+    }
+    points
+}
+
+/// The ends of the given range in increasing order, so that reversed ranges work like their
+/// normal counterparts
+fn ordered_range<Num: emath::Numeric>(range: &RangeInclusive<Num>) -> (f64, f64) {
+    let (start, end) = (range.start().to_f64(), range.end().to_f64());
+    if start > end {
+        (end, start)
+    } else {
+        (start, end)
+    }
+}
+
+/// The given range as floats, keeping its ends in the order they are given
+fn to_f64_range<Num: emath::Numeric>(range: &RangeInclusive<Num>) -> RangeInclusive<f64> {
+    range.start().to_f64()..=range.end().to_f64()
+}
+
+/// Whether values can be placed on a scale from the given minimum to the given maximum, which
+/// has to cover some values and be finite
+fn is_valid_range(min_value: f64, max_value: f64) -> bool {
+    min_value.is_finite() && max_value.is_finite() && min_value < max_value
+}
+
+/// Blend between two colors, where `t` of 0 gives `a` and 1 gives `b`
+fn mix(a: Color32, b: Color32, t: f32) -> Color32 {
+    let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+    Color32::from_rgba_premultiplied(
+        channel(a.r(), b.r()),
+        channel(a.g(), b.g()),
+        channel(a.b(), b.b()),
+        channel(a.a(), b.a()),
+    )
+}
+
+/// Reads the bound value when given `None`, and writes it when given a new value
+struct Binding<'a>(Box<dyn 'a + FnMut(Option<f64>) -> f64>);
+
+impl std::fmt::Debug for Binding<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Binding")
+    }
+}
+
+/// What a gauge showed, returned by [`Gauge::show`] for drawing overlays on top of the gauge or
+/// reacting to what it shows without working out its geometry again, and passed to
+/// [`Gauge::on_hover`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct GaugeOutput {
+    /// The value of the gauge
+    pub value: f64,
+    /// The range of values covered by the gauge
+    pub range: RangeInclusive<f64>,
+    /// The units of the value, or empty if they weren't given
+    pub units: String,
+    /// The rect allocated for the gauge, including the labels around the arc
+    pub rect: Rect,
+    /// The center of the knob at the end of the value arc
+    pub indicator: Pos2,
+    /// The zone the value is inside of, if any
+    pub zone: Option<Zone>,
+    /// Whether the value is outside of the range, so that the indicator stopped at the end of the
+    /// arc
+    pub clamped: bool,
+}
+
+/// A function showing the contents of a tooltip, given what the gauge shows
+type HoverUi<'a> = dyn 'a + FnMut(&mut Ui, &GaugeOutput);
+
+/// Shows the contents of the tooltip of a gauge
+struct HoverCallback<'a>(Box<HoverUi<'a>>);
+
+impl std::fmt::Debug for HoverCallback<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HoverCallback")
+    }
+}
+
+/// A function called with the zone the value was in and the zone it is in now
+type ZoneChange<'a> = dyn 'a + FnMut(Option<&Zone>, Option<&Zone>);
+
+/// Called whenever the value moves from one zone to another
+struct ZoneCallback<'a>(Box<ZoneChange<'a>>);
+
+impl std::fmt::Debug for ZoneCallback<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ZoneCallback")
+    }
+}
+
+/// The configuration the static parts of a gauge are painted from, which is part of the keys
+/// their shapes are cached by
+#[derive(Hash)]
+struct StaticInputs<'k> {
+    size: Keyed<[f32; 3]>,
+    range: Keyed<[f64; 2]>,
+    dial: Dial,
+    labels: scale::Labels,
+    zones: Keyed<&'k [Zone]>,
+    style: Keyed<&'k GaugeStyle>,
+    compact: bool,
+    dense: bool,
+    low_overhead: bool,
+    skirt_text: &'k str,
+    legend: &'k Option<(String, String)>,
+}
+
+/// Everything a cacheable gauge is painted from, which is the key its shapes are cached by. It
+/// leaves out the time, which they don't depend on while the gauge is cacheable.
+#[derive(Hash)]
+struct PaintInputs<'k> {
+    rect: Keyed<Rect>,
+    has_focus: bool,
+    static_inputs: StaticInputs<'k>,
+    values: Keyed<[f64; 2]>,
+    integral: bool,
+    color: Color32,
+    text: &'k str,
+    units: &'k str,
+    previous_zone: Option<usize>,
+    zone_text_color: bool,
+    editing_zones: bool,
+    overflow: OverflowBehavior,
+    latch_alarm: bool,
+    debug: bool,
+    show_watermarks: bool,
+    show_statistics: bool,
+    stale_indicator: bool,
+    mirrored: bool,
+    /// The blink and pulse rates and the brightness
+    rates: Keyed<[Option<f32>; 3]>,
+    /// The linked hover, peak decay, target, redline and odometer
+    indicators: Keyed<[Option<f64>; 5]>,
+    deviation_band: Option<(Color32, Color32)>,
+    markers: Keyed<&'k [(f64, MarkerStyle)]>,
+    second_value: Keyed<Option<(f64, Color32)>>,
+    segments: Keyed<&'k [(String, f64, Color32)]>,
+    last_valid_value: Keyed<Option<f64>>,
+    peak: Keyed<Option<f64>>,
+    watermarks: Keyed<Option<(f64, f64)>>,
+    alarm_latched: bool,
+    statistics: Keyed<Option<&'k Statistics>>,
+    visuals: VisualsInputs,
+}
+
+/// The parts of egui's visuals gauges are painted with
+#[derive(Hash)]
+struct VisualsInputs {
+    dark_mode: bool,
+    colors: [Color32; 6],
+    selection_width: Keyed<f32>,
+}
+
+impl VisualsInputs {
+    fn new(visuals: &Visuals) -> Self {
+        Self {
+            dark_mode: visuals.dark_mode,
+            colors: [
+                visuals.widgets.noninteractive.text_color(),
+                track_color(visuals),
+                visuals.error_fg_color,
+                visuals.selection.bg_fill,
+                visuals.selection.stroke.color,
+                visuals.extreme_bg_color,
+            ],
+            selection_width: Keyed(visuals.selection.stroke.width),
+        }
+    }
+}
+
+/// The lengths the dial of a gauge is laid out with, which follow from its size and style
+#[derive(Clone, Copy, Debug)]
+struct DialGeometry {
+    radius: f32,
+    thickness: f32,
+    text_clearance: f32,
+    legend_height: f32,
+}
+
+impl DialGeometry {
+    /// The part of the rect allocated for the gauge which the arc is laid out in, leaving room for
+    /// the labels around it
+    fn arc_rect(&self, outer_rect: Rect) -> Rect {
+        let mut rect = outer_rect;
+        rect.max.y -= self.legend_height;
+        rect.shrink(self.text_clearance)
+    }
+}
 
-pub struct Gauge {
+/// The gauge widget. Since it can hold a binding to a value, it can't be cloned or compared; keep
+/// the data it is created from around instead, and create a gauge from it every frame. Gauges
+/// without a borrowed binding can also be configured once and kept around, and shown with
+/// `ui.add(&mut gauge)` after updating the value with [`Self::set_value`].
+#[derive(Debug)]
+pub struct Gauge<'a> {
     value: f64,
+    shown_value: f64,
+    binding: Option<Binding<'a>>,
     min_value: f64,
     max_value: f64,
+    integral: bool,
     size: f32,
     color: Color32,
     text: String,
+    rate_of_change: Option<f64>,
+    sparkline: Option<usize>,
+    scroll_step: Option<f64>,
+    units: String,
+    labels: scale::Labels,
+    show_tooltip: bool,
+    on_hover: Option<HoverCallback<'a>>,
+    default_value: Option<f64>,
+    step: Option<f64>,
+    drag_sensitivity: f64,
+    sense: Sense,
+    context_menu: bool,
+    animation_time: Option<f32>,
+    easing: Easing,
+    spring: Option<Spring>,
+    zones: Vec<Zone>,
+    zone_edit: Option<&'a mut Vec<Zone>>,
+    on_zone_change: Option<ZoneCallback<'a>>,
+    previous_zone: Option<usize>,
+    zone_text_color: bool,
+    latch_alarm: bool,
+    announce_alarms: bool,
+    debug: bool,
+    link_group: Option<Id>,
+    linked_hover: Option<f64>,
+    blink_rate: Option<f32>,
+    pulse_rate: Option<f32>,
+    flash_threshold: Option<f64>,
+    peak_decay: Option<f64>,
+    watermarks: bool,
+    statistics: bool,
+    target: Option<f64>,
+    deviation_band: Option<(Color32, Color32)>,
+    markers: Vec<(f64, MarkerStyle)>,
+    redline: Option<f64>,
+    second_value: Option<(f64, Color32)>,
+    segments: Vec<(String, f64, Color32)>,
+    legend: Option<(String, String)>,
+    skirt_text: String,
+    brightness: Option<f32>,
+    odometer: Option<f64>,
+    trail: Option<usize>,
+    id_source: Option<Id>,
+    id: Option<Id>,
+    dial: Dial,
+    fit: Option<Fit>,
+    aspect_ratio: f32,
+    compact: Option<bool>,
+    dense: bool,
+    smoothing: Option<Smoothing>,
+    sampled_at: Option<(Instant, Interpolation)>,
+    repaint_precision: Option<f64>,
+    low_overhead: bool,
+    overflow: OverflowBehavior,
+    stale_indicator: bool,
+    style: GaugeStyle,
+    #[cfg(feature = "watch")]
+    watch: Option<watch::Receiver<f64>>,
+    pixels_per_point: f32,
+    right_to_left: Option<bool>,
+    layout_right_to_left: bool,
+    /// The dial the gauge was last shown with, once it fit itself to the available space and
+    /// took on the theme
+    laid_out: Option<DialGeometry>,
 }
 
-impl Gauge {
+impl<'a> Gauge<'a> {
     /// Create a gauge which displays the given value as part of the given range. The given size is
     /// with width and height of the gauge. The given color is the color used for the value
     /// indicator arc.
@@ -25,316 +487,3170 @@ impl Gauge {
         size: f32,
         color: Color32,
     ) -> Self {
+        let (min_value, max_value) = ordered_range(&range);
         Self {
             value: value.to_f64(),
-            min_value: range.start().to_f64(),
-            max_value: range.end().to_f64(),
+            shown_value: value.to_f64(),
+            binding: None,
+            min_value,
+            max_value,
+            integral: Num::INTEGRAL,
             size,
             color,
             text: Default::default(),
+            rate_of_change: None,
+            sparkline: None,
+            scroll_step: None,
+            units: Default::default(),
+            labels: Default::default(),
+            show_tooltip: false,
+            on_hover: None,
+            default_value: None,
+            step: None,
+            drag_sensitivity: 1.0,
+            sense: Sense::hover(),
+            context_menu: false,
+            animation_time: None,
+            easing: Easing::default(),
+            spring: None,
+            zones: Vec::new(),
+            zone_edit: None,
+            on_zone_change: None,
+            previous_zone: None,
+            zone_text_color: false,
+            latch_alarm: false,
+            announce_alarms: false,
+            debug: false,
+            link_group: None,
+            linked_hover: None,
+            blink_rate: None,
+            pulse_rate: None,
+            flash_threshold: None,
+            peak_decay: None,
+            watermarks: false,
+            statistics: false,
+            target: None,
+            deviation_band: None,
+            markers: Vec::new(),
+            redline: None,
+            second_value: None,
+            segments: Vec::new(),
+            legend: None,
+            skirt_text: String::new(),
+            brightness: None,
+            odometer: None,
+            trail: None,
+            id_source: None,
+            id: None,
+            dial: Dial::default(),
+            fit: None,
+            aspect_ratio: 1.0,
+            compact: None,
+            dense: false,
+            smoothing: None,
+            sampled_at: None,
+            repaint_precision: None,
+            low_overhead: false,
+            overflow: OverflowBehavior::default(),
+            stale_indicator: false,
+            style: GaugeStyle::default(),
+            #[cfg(feature = "watch")]
+            watch: None,
+            pixels_per_point: 1.0,
+            right_to_left: None,
+            layout_right_to_left: false,
+            laid_out: None,
         }
     }
 
+    /// Create a small gauge for dashboards with dozens of them in a grid. It uses the compact
+    /// layout with a thinner arc and no labels around the outside, which leaves room for the
+    /// value to be readable at 60 to 90 points. It starts out 72 points wide, which can be
+    /// changed with [`Self::size`].
+    pub fn new_compact<Num: emath::Numeric>(
+        value: Num,
+        range: RangeInclusive<Num>,
+        color: Color32,
+    ) -> Self {
+        let mut gauge = Self::new(value, range, DENSE_SIZE, color).compact(true);
+        gauge.dense = true;
+        gauge
+    }
+
+    /// Create a gauge which displays the current value of the given atomic, for values published
+    /// by another thread. Keep the atomic in an `Arc` and pass it to the gauge each frame.
+    pub fn from_atomic<Num: emath::Numeric>(
+        value: &impl AtomicValue,
+        range: RangeInclusive<Num>,
+        size: f32,
+        color: Color32,
+    ) -> Self {
+        let mut gauge = Self::new(value.load_f64(), to_f64_range(&range), size, color);
+        gauge.integral = Num::INTEGRAL;
+        gauge
+    }
+
+    /// Create a gauge which displays a new sample of the given source. The range is the one hinted
+    /// by the source, or 0 to 1 when it doesn't give one, and can be changed with
+    /// [`Self::range`].
+    pub fn from_source(source: &mut impl GaugeSource, size: f32, color: Color32) -> Self {
+        let range = source.range_hint().unwrap_or(0.0..=1.0);
+        Self::new(source.sample(), range, size, color)
+    }
+
+    /// Create a gauge which displays the latest value sent on the given `tokio::sync::watch`
+    /// channel. The gauge checks the channel ten times a second while it is shown, so values sent
+    /// from async tasks show up even while nothing else repaints, with or without a runtime.
+    #[cfg(feature = "watch")]
+    pub fn from_watch<Num: emath::Numeric>(
+        receiver: &mut watch::Receiver<f64>,
+        range: RangeInclusive<Num>,
+        size: f32,
+        color: Color32,
+    ) -> Self {
+        let value = *receiver.borrow_and_update();
+        let mut gauge = Self::new(value, to_f64_range(&range), size, color);
+        gauge.integral = Num::INTEGRAL;
+        gauge.watch = Some(receiver.clone());
+        gauge
+    }
+
+    /// Create an interactive gauge which can be dragged to change the given value, much like a
+    /// `Slider`. Edits are reported through [`Response::changed`], and dragging through the usual
+    /// [`Response::dragged`], [`Response::drag_started`] and [`Response::drag_stopped`]. The new
+    /// value is written back to the given one.
+    pub fn new_mut<Num: emath::Numeric>(
+        value: &'a mut Num,
+        range: RangeInclusive<Num>,
+        size: f32,
+        color: Color32,
+    ) -> Self {
+        Self::from_get_set(range, size, color, move |new_value: Option<f64>| {
+            if let Some(new_value) = new_value {
+                *value = Num::from_f64(new_value);
+            }
+            value.to_f64()
+        })
+    }
+
+    /// Create an interactive gauge like [`Self::new_mut`], but for a value behind an accessor,
+    /// lock or the like. Like with `Slider::from_get_set`, the given function is called with
+    /// `None` to read the value and with `Some` new value to write it, and returns the value. A
+    /// range of integers makes the gauge step and write its value in whole numbers.
+    pub fn from_get_set<Num: emath::Numeric>(
+        range: RangeInclusive<Num>,
+        size: f32,
+        color: Color32,
+        mut get_set_value: impl 'a + FnMut(Option<f64>) -> f64,
+    ) -> Self {
+        let value = get_set_value(None);
+        let mut gauge = Self::new(value, to_f64_range(&range), size, color);
+        gauge.integral = Num::INTEGRAL;
+        gauge.binding = Some(Binding(Box::new(get_set_value)));
+        gauge
+    }
+
+    /// Change the value shown by a gauge which is kept between frames. A gauge with a binding
+    /// reads its value from the binding instead.
+    pub fn set_value(&mut self, value: impl emath::Numeric) {
+        self.value = value.to_f64();
+        self.shown_value = self.value;
+    }
+
+    /// Change the value shown by the gauge
+    pub fn value(mut self, value: impl emath::Numeric) -> Self {
+        self.set_value(value);
+        self
+    }
+
+    /// Change the width and height of the gauge
+    pub fn size(mut self, size: f32) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Change the color of the value indicator arc
+    pub fn color(mut self, color: Color32) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Change the range of values covered by the gauge
+    pub fn range<Num: emath::Numeric>(mut self, range: RangeInclusive<Num>) -> Self {
+        (self.min_value, self.max_value) = ordered_range(&range);
+        self
+    }
+
+    /// Use the range, labels, units and zones of the given scale, replacing any zones added
+    /// before
+    pub fn scale(mut self, scale: &Scale) -> Self {
+        self.min_value = scale.min_value;
+        self.max_value = scale.max_value;
+        self.labels = scale.labels;
+        self.units = scale.units.clone();
+        self.zones = scale.zones.clone();
+        self
+    }
+
     /// Text to be displayed under the value in the center of the gauge
     pub fn text(mut self, text: impl Into<String>) -> Self {
         self.text = text.into();
         self
     }
 
-    fn inner_width(&self) -> f32 {
-        self.size - self.text_clearance() * 2.0
+    /// Show a thin secondary needle on an inner radius indicating how fast the value is changing.
+    /// The given rate (in value units per second) is the rate at which the needle is fully
+    /// deflected; the needle points straight up when the value is steady.
+    pub fn rate_of_change(mut self, full_scale: f64) -> Self {
+        self.rate_of_change = Some(full_scale);
+        self
     }
 
-    fn text_clearance(&self) -> f32 {
-        self.size / 10.0
+    /// Draw a small line graph of the last `len` distinct values in the center of the gauge, under
+    /// the value.
+    pub fn sparkline(mut self, len: usize) -> Self {
+        self.sparkline = Some(len);
+        self
     }
 
-    fn x_f(&self, rect: Rect, angle: i32, radius: f32) -> f32 {
-        self.center(rect).x + (angle as f32 * PI / 180.0).cos() * radius
+    /// How much each notch of the scroll wheel or press of an arrow key changes the value of an
+    /// interactive gauge. Defaults to the step set by [`Self::step_by`], or otherwise a hundredth
+    /// of the range.
+    pub fn scroll_step(mut self, step: f64) -> Self {
+        self.scroll_step = Some(step);
+        self
     }
 
-    fn y_f(&self, rect: Rect, angle: i32, radius: f32) -> f32 {
-        self.center(rect).y - (angle as f32 * PI / 180.0).sin() * radius
+    /// Units of the value, e.g. "°C" or "rpm", shown alongside the exact value in the tooltip
+    pub fn units(mut self, units: impl Into<String>) -> Self {
+        self.units = units.into();
+        self
     }
 
-    fn radius(&self) -> f32 {
-        self.inner_width() / 2.0
+    /// Show the exact value and the range of the gauge in a tooltip when it is hovered
+    pub fn show_tooltip(mut self, show_tooltip: bool) -> Self {
+        self.show_tooltip = show_tooltip;
+        self
     }
 
-    fn thickness(&self) -> f32 {
-        self.inner_width() / 15.0
+    /// Show a tooltip with contents of the app's own when the gauge is hovered, like a history
+    /// of the value or links to more details, given what the gauge shows. This can be combined
+    /// with [`Self::show_tooltip`], which comes first.
+    pub fn on_hover(mut self, on_hover: impl 'a + FnMut(&mut Ui, &GaugeOutput)) -> Self {
+        self.on_hover = Some(HoverCallback(Box::new(on_hover)));
+        self
     }
 
-    fn center(&self, rect: Rect) -> Pos2 {
-        Pos2 {
-            x: rect.left() + rect.width() / 2.0,
-            y: rect.bottom() - rect.height() / 2.0,
-        }
+    /// The value an interactive gauge is reset to when it is double-clicked
+    pub fn default_value(mut self, value: impl emath::Numeric) -> Self {
+        self.default_value = Some(value.to_f64());
+        self
     }
 
-    fn value_to_angle(&self, v: f64) -> i32 {
-        ((270.0 - ((v - self.min_value) / (self.max_value - self.min_value)) * 270.0) - 45.0) as i32
+    /// Snap values set by interacting with the gauge to multiples of the given step, counted from
+    /// the start of the range. A step of zero disables snapping.
+    pub fn step_by(mut self, step: f64) -> Self {
+        self.step = (step != 0.0).then_some(step);
+        self
     }
 
-    fn angle(&self) -> i32 {
-        self.value_to_angle(self.value)
+    /// Scale how much the value of an interactive gauge changes as the pointer is dragged around
+    /// the arc. At the default of 1.0 the indicator follows the pointer. Holding shift while
+    /// dragging scales this down further for fine adjustment.
+    pub fn drag_sensitivity(mut self, sensitivity: f64) -> Self {
+        self.drag_sensitivity = sensitivity;
+        self
     }
 
-    fn paint(&mut self, ui: &mut Ui, outer_rect: Rect) {
-        let rect = Rect {
-            min: Pos2 {
-                x: outer_rect.min.x + self.text_clearance(),
-                y: outer_rect.min.y + self.text_clearance(),
-            },
-            max: Pos2 {
-                x: outer_rect.max.x - self.text_clearance(),
-                y: outer_rect.max.y - self.text_clearance(),
-            },
-        };
+    /// Additionally sense these interactions, e.g. `Sense::click()` so that a display-only gauge
+    /// can have a context menu attached via [`Response::context_menu`].
+    pub fn sense(mut self, sense: Sense) -> Self {
+        self.sense = sense;
+        self
+    }
+
+    /// Show a built-in context menu when the gauge is right-clicked, offering to copy the value
+    /// and, for interactive gauges with a [`Self::default_value`], to reset it. Don't also attach
+    /// a context menu of your own to the returned response.
+    pub fn context_menu(mut self, context_menu: bool) -> Self {
+        self.context_menu = context_menu;
+        self
+    }
+
+    /// Smoothly move the arc and indicator to new values instead of jumping straight to them. The
+    /// value in the center is always shown exactly.
+    pub fn animated(mut self, animated: bool) -> Self {
+        self.animation_time = animated.then(|| self.animation_time.unwrap_or(0.5));
+        self
+    }
 
-        let visuals = ui.style().noninteractive();
+    /// How long in seconds it takes an animated gauge to move to a new value. Implies
+    /// [`Self::animated`].
+    pub fn animation_time(mut self, seconds: f32) -> Self {
+        self.animation_time = Some(seconds);
+        self
+    }
 
-        // uncomment to show bounding rect for debugging
-        // ui.painter()
-        //  .rect(outer_rect, 0.0, visuals.bg_fill, visuals.bg_stroke);
+    /// The curve an animated gauge follows when moving to a new value. Implies
+    /// [`Self::animated`].
+    pub fn easing(mut self, easing: Easing) -> Self {
+        self.easing = easing;
+        self.animated(true)
+    }
 
-        let text_color = visuals.text_color();
-        let arc_bg_color = if ui.visuals().dark_mode {
-            Color32::WHITE
-        } else {
-            Color32::GRAY
-        };
-        let bg_color = visuals.bg_fill;
+    /// Move the arc and indicator like a needle on a damped spring, overshooting slightly and
+    /// settling on new values. This takes precedence over [`Self::animated`].
+    pub fn spring(mut self, spring: Spring) -> Self {
+        self.spring = Some(spring);
+        self
+    }
 
-        self.paint_background_circle(ui, rect, arc_bg_color, bg_color);
-        self.paint_colored_circle(ui, rect, bg_color);
-        self.paint_center_mask(ui, rect, bg_color);
-        self.paint_skirt_mask(ui, rect, bg_color);
-        self.paint_end_caps(ui, rect, bg_color, arc_bg_color);
-        self.paint_value_circle(ui, rect);
-        self.write_center_value(ui, rect, text_color);
-        self.write_values_around_circle(ui, rect, text_color);
+    /// Add a zone to the gauge. When zones overlap, the one added last wins.
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
 
-        if !self.text.is_empty() {
-            self.write_text(ui, rect, text_color);
+    /// Let the user tune the zones by dragging the boundaries between them along the arc, like
+    /// alarm limits set on the instrument itself. The gauge shows the given zones, replacing any
+    /// added before, with a handle on every boundary. Dragging a handle moves the ends of all the
+    /// zones which meet there, writes them back and marks the response as changed. Ends can't be
+    /// dragged past the other end of their zone.
+    pub fn edit_zones(mut self, zones: &'a mut Vec<Zone>) -> Self {
+        self.zones = zones.clone();
+        self.zone_edit = Some(zones);
+        self
+    }
+
+    /// The distinct ends of the zones which are inside of the range, where the handles for
+    /// editing the zones are
+    fn zone_boundaries(&self) -> Vec<f64> {
+        let mut boundaries: Vec<f64> = self
+            .zones
+            .iter()
+            .flat_map(|zone| [*zone.range.start(), *zone.range.end()])
+            .filter(|value| (self.min_value..=self.max_value).contains(value))
+            .collect();
+        boundaries.sort_by(f64::total_cmp);
+        boundaries.dedup();
+        boundaries
+    }
+
+    /// The zone boundary with its handle at the given position, if any
+    fn zone_boundary_at_pos(&self, rect: Rect, pos: Pos2) -> Option<f64> {
+        let distance = self.offset_from_center(rect, pos).length();
+        if distance < self.radius() - self.thickness()
+            || distance > self.radius() + self.zone_band_width() * 2.5 + self.thickness() / 2.0
+        {
+            return None;
         }
+        let sweep = self.pos_to_sweep(rect, pos);
+        let full_sweep = self.dial.sweep() as f32;
+        self.zone_boundaries()
+            .into_iter()
+            .map(|boundary| {
+                let fraction = (boundary - self.min_value) / (self.max_value - self.min_value);
+                // how far along the arc the pointer is from the boundary
+                let along =
+                    (fraction as f32 * full_sweep - sweep).abs().to_radians() * self.radius();
+                (boundary, along)
+            })
+            .filter(|&(_, along)| along < self.thickness())
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(boundary, _)| boundary)
     }
 
-    fn write_text(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
-        let center = self.center(rect);
-        let wrap_width = self.inner_width() * 2.0 / 3.0;
-        let text = ui.painter().layout(
-            self.text.clone(),
-            FontId {
-                size: self.inner_width() / 10.0,
-                family: FontFamily::Monospace,
-            },
-            text_color,
-            wrap_width,
-        );
-        let visuals = ui.style().noninteractive();
-        ui.painter().galley(
-            Pos2 {
-                x: center.x - text.rect.width() / 2.0,
-                y: center.y + self.inner_width() / 5.0 - text.rect.height() / 2.0,
-            },
-            text,
-            visuals.bg_fill
-        );
+    /// Drag the zone boundary which was grabbed, returning whether one is being dragged so that
+    /// the drag doesn't also change the value
+    fn handle_zone_editing(&mut self, ui: &Ui, rect: Rect, response: &mut Response) -> bool {
+        if self.zone_edit.is_none() || !self.has_valid_range() {
+            return false;
+        }
+        let id = response.id.with("zone boundary");
+        if response.drag_started() {
+            let grabbed = ui
+                .input(|i| i.pointer.press_origin())
+                .and_then(|pos| self.zone_boundary_at_pos(rect, pos));
+            ui.data_mut(|d| match grabbed {
+                Some(boundary) => d.insert_temp(id, boundary),
+                None => d.remove::<f64>(id),
+            });
+        }
+        let Some(boundary) = ui.data(|d| d.get_temp::<f64>(id)) else {
+            if response
+                .hover_pos()
+                .is_some_and(|pos| self.zone_boundary_at_pos(rect, pos).is_some())
+            {
+                ui.ctx().set_cursor_icon(egui::CursorIcon::Grab);
+            }
+            return false;
+        };
+        if !response.dragged() {
+            ui.data_mut(|d| d.remove::<f64>(id));
+            return response.drag_stopped();
+        }
+        ui.ctx().set_cursor_icon(egui::CursorIcon::Grabbing);
+
+        let Some(pos) = response.interact_pointer_pos() else {
+            return true;
+        };
+        // keep the zones which meet at the boundary from turning inside out
+        let low = self
+            .zones
+            .iter()
+            .filter(|zone| *zone.range.end() == boundary)
+            .map(|zone| *zone.range.start())
+            .fold(self.min_value, f64::max);
+        let high = self
+            .zones
+            .iter()
+            .filter(|zone| *zone.range.start() == boundary)
+            .map(|zone| *zone.range.end())
+            .fold(self.max_value, f64::min);
+        let value = self
+            .snap_to_step(self.pos_to_value(rect, pos))
+            .max(low)
+            .min(high);
+        if value != boundary {
+            for zone in &mut self.zones {
+                let (mut start, mut end) = (*zone.range.start(), *zone.range.end());
+                if start == boundary {
+                    start = value;
+                }
+                if end == boundary {
+                    end = value;
+                }
+                zone.range = start..=end;
+            }
+            if let Some(zones) = &mut self.zone_edit {
+                zones.clone_from(&self.zones);
+            }
+            ui.data_mut(|d| d.insert_temp(id, value));
+            response.mark_changed();
+        }
+        true
     }
 
-    fn write_values_around_circle(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
-        let mut value = self.min_value;
-        loop {
-            let angle = self.value_to_angle(value);
-            ui.painter().text(
+    fn paint_zone_handles(&self, canvas: &mut Canvas, rect: Rect, color: Color32) {
+        let radius = self.radius() + self.zone_band_width() * 1.5;
+        for boundary in self.zone_boundaries() {
+            let angle = self.value_to_angle(boundary);
+            canvas.circle(
                 Pos2 {
-                    x: self.x_f(rect, angle, self.radius() + self.thickness()),
-                    y: self.y_f(rect, angle, self.radius() + self.thickness()),
+                    x: self.x_f(rect, angle, radius),
+                    y: self.y_f(rect, angle, radius),
                 },
-                Align2::CENTER_CENTER,
-                (value as i32).to_string(),
-                FontId {
-                    size: self.inner_width() / 15.0,
-                    family: FontFamily::Monospace,
-                },
-                text_color,
+                (self.zone_band_width() * 1.5).max(3.0),
+                color,
+                Stroke::NONE,
             );
-            if value == self.max_value {
-                break;
-            }
-            value += (self.max_value - self.min_value) / 6.0;
-            if (self.max_value - value) < 1.0 {
-                value = self.max_value;
-            }
         }
     }
 
-    fn write_center_value(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
-        ui.painter().text(
-            self.center(rect),
-            Align2::CENTER_CENTER,
-            self.value.to_string(),
-            FontId {
-                size: self.inner_width() / 5.0,
-                family: FontFamily::Monospace,
-            },
-            text_color,
-        );
+    /// Call the given function whenever the value moves from one zone to another, with the zone
+    /// it was in and the one it is in now (`None` being outside of all zones), for logging or
+    /// sounding alerts. The zone the value is in when the gauge is first shown isn't reported,
+    /// and neither are frames where the value is NaN or infinite.
+    pub fn on_zone_change(
+        mut self,
+        on_zone_change: impl 'a + FnMut(Option<&Zone>, Option<&Zone>),
+    ) -> Self {
+        self.on_zone_change = Some(ZoneCallback(Box::new(on_zone_change)));
+        self
     }
 
-    fn paint_value_circle(&mut self, ui: &mut Ui, rect: Rect) {
-        ui.painter().circle(
-            Pos2 {
-                x: self.x_f(rect, self.angle(), self.radius() - self.thickness() / 2.0),
-                y: self.y_f(rect, self.angle(), self.radius() - self.thickness() / 2.0),
-            },
-            self.thickness() / 2.0,
-            Color32::WHITE,
-            Stroke {
-                width: 1.0,
-                color: self.color,
-            },
-        );
+    /// Show the value in the center in the color of the zone it is in, so that a reading in a red
+    /// zone is unmistakably red even where the arc is small. Zones with their own
+    /// [`Zone::text_color`] use that instead, whether or not this is set.
+    pub fn zone_text_color(mut self, zone_text_color: bool) -> Self {
+        self.zone_text_color = zone_text_color;
+        self
     }
 
-    fn paint_end_caps(
-        &mut self,
-        ui: &mut Ui,
-        rect: Rect,
-        bg_color: Color32,
-        arc_bg_color: Color32,
-    ) {
-        ui.painter().circle(
-            Pos2 {
-                x: self.x_f(rect, 225, self.radius() - self.thickness() / 2.0),
-                y: self.y_f(rect, 225, self.radius() - self.thickness() / 2.0),
-            },
-            self.thickness() / 2.0,
-            self.color,
-            Stroke {
-                width: 0.0,
-                color: bg_color,
-            },
-        );
-        ui.painter().circle(
-            Pos2 {
-                x: self.x_f(rect, -45, self.radius() - self.thickness() / 2.0),
-                y: self.y_f(rect, -45, self.radius() - self.thickness() / 2.0),
-            },
-            self.thickness() / 2.0,
-            arc_bg_color,
-            Stroke {
-                width: 0.0,
-                color: bg_color,
-            },
-        );
+    /// Blink the arc the given number of times per second while the value is inside of a warning
+    /// or critical zone
+    pub fn blink(mut self, rate: f32) -> Self {
+        self.blink_rate = Some(rate);
+        self
     }
 
-    fn paint_center_mask(&mut self, ui: &mut Ui, rect: Rect, bg_color: Color32) {
-        ui.painter().add(Shape::Path(PathShape {
-            points: (-45..=225)
-                .map(|angle: i32| Pos2 {
-                    x: self.x_f(rect, angle, self.radius() - self.thickness()),
-                    y: self.y_f(rect, angle, self.radius() - self.thickness()),
-                })
-                .collect(),
-            closed: true,
-            fill: bg_color,
-            stroke: Stroke {
-                width: 0.0,
-                color: bg_color,
-            },
-        }));
+    /// Smoothly pulse the brightness of the arc the given number of times per second while the
+    /// value is inside of a critical zone
+    pub fn pulse(mut self, rate: f32) -> Self {
+        self.pulse_rate = Some(rate);
+        self
     }
 
-    fn paint_colored_circle(&mut self, ui: &mut Ui, rect: Rect, bg_color: Color32) {
-        ui.painter().add(Shape::Path(PathShape {
-            points: (self.angle()..=225)
-                .map(|angle: i32| Pos2 {
-                    x: self.x_f(rect, angle, self.radius()),
-                    y: self.y_f(rect, angle, self.radius()),
-                })
-                .chain(std::iter::once(self.center(rect)))
-                .collect(),
-            closed: true,
-            fill: self.color,
-            stroke: Stroke {
-                width: 0.0,
-                color: bg_color,
-            },
-        }));
+    /// Briefly highlight the value in the center whenever it changes by more than the given
+    /// amount from one frame to the next, so that infrequent updates are noticeable
+    pub fn flash_on_change(mut self, threshold: f64) -> Self {
+        self.flash_threshold = Some(threshold);
+        self
     }
 
-    fn paint_background_circle(
-        &mut self,
-        ui: &mut Ui,
-        rect: Rect,
-        arc_bg_color: Color32,
-        bg_color: Color32,
-    ) {
-        ui.painter().add(Shape::Path(PathShape {
-            points: (-45..=225)
-                .map(|angle: i32| Pos2 {
-                    x: self.x_f(rect, angle, self.radius()),
-                    y: self.y_f(rect, angle, self.radius()),
-                })
-                .chain(std::iter::once(self.center(rect)))
-                .collect(),
-            closed: true,
-            fill: arc_bg_color,
-            stroke: Stroke {
-                width: 0.0,
-                color: bg_color,
-            },
-        }));
+    /// Mark the highest value seen on the arc. The mark falls back towards the value at the given
+    /// rate in value units per second, or stays put forever if the rate is zero.
+    pub fn peak_hold(mut self, decay: f64) -> Self {
+        self.peak_decay = Some(decay);
+        self
     }
 
-    fn paint_skirt_mask(&mut self, ui: &mut Ui, rect: Rect, bg_color: Color32) {
-        ui.painter().add(Shape::Path(PathShape {
-            points: vec![
-                Pos2 {
-                    x: self.x_f(rect, -45, self.radius()),
-                    y: self.y_f(rect, -45, self.radius()),
-                },
-                Pos2 {
-                    x: self.x_f(rect, 225, self.radius()),
-                    y: self.y_f(rect, 225, self.radius()),
-                },
-                Pos2 {
-                    x: self.x_f(rect, 225, self.radius() - self.thickness()),
-                    y: self.y_f(rect, 225, self.radius() - self.thickness()),
+    /// Mark the lowest and highest values seen on the arc, until cleared with
+    /// [`Self::reset_watermarks`]
+    pub fn watermarks(mut self, watermarks: bool) -> Self {
+        self.watermarks = watermarks;
+        self
+    }
+
+    /// Forget the lowest and highest values seen by the gauge with the given id, which is the id
+    /// of the response it returned (see [`Self::id_source`])
+    pub fn reset_watermarks(ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| PersistedState::get(d, id).watermarks = None);
+    }
+
+    /// Show the lowest, average and highest of the values the gauge was shown with in a row under
+    /// the value, until cleared with [`Self::reset_statistics`]. Every frame counts as one
+    /// value for the average, and frames without a value (NaN or infinite) are left out.
+    pub fn statistics(mut self, statistics: bool) -> Self {
+        self.statistics = statistics;
+        self
+    }
+
+    /// Forget the statistics of the gauge with the given id, which is the id of the response it
+    /// returned (see [`Self::id_source`])
+    pub fn reset_statistics(ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| PersistedState::get(d, id).statistics = None);
+    }
+
+    /// Once the value enters a critical zone, keep the gauge outlined in the error color of the
+    /// egui visuals until the alarm is acknowledged, even if the value recovers in the meantime.
+    /// Alarms are acknowledged by clicking the gauge or with [`Self::acknowledge`].
+    pub fn latch_alarm(mut self, latch_alarm: bool) -> Self {
+        self.latch_alarm = latch_alarm;
+        self
+    }
+
+    /// Tell screen readers when the value enters or leaves a warning or critical zone, like
+    /// "Boiler pressure entered critical zone", as an AccessKit live region which is announced as
+    /// soon as it changes. This only has an effect with the `accesskit` feature, and once the
+    /// integration has turned AccessKit on.
+    pub fn announce_alarms(mut self, announce_alarms: bool) -> Self {
+        self.announce_alarms = announce_alarms;
+        self
+    }
+
+    /// The announcement for the value moving from one zone to another, if either of them is an
+    /// alarm
+    fn alarm_announcement(&self, previous: Option<usize>, zone: Option<usize>) -> Option<String> {
+        let name = if self.text.is_empty() {
+            "Gauge"
+        } else {
+            &self.text
+        };
+        let zone_at = |index: Option<usize>| index.and_then(|i| self.zones.get(i));
+        match (zone_at(previous), zone_at(zone)) {
+            (_, Some(zone)) if zone.is_alarm() => {
+                Some(format!("{name} entered {}", zone.description()))
+            }
+            (Some(previous), _) if previous.is_alarm() => {
+                Some(format!("{name} left {}", previous.description()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Keep the latest announcement in the accessibility tree, where changing it makes screen
+    /// readers read it out
+    #[cfg(feature = "accesskit")]
+    fn announce(&self, ui: &Ui, id: Id, state: &GaugeState) {
+        use egui::accesskit::{Live, Role};
+
+        if let Some(announcement) = &state.announcement {
+            ui.ctx()
+                .accesskit_node_builder(id.with("announcement"), |builder| {
+                    builder.set_role(Role::Alert);
+                    builder.set_live(Live::Assertive);
+                    builder.set_name(announcement.clone());
+                });
+        }
+    }
+
+    /// Acknowledge the latched alarm of the gauge with the given id, which is the id of the
+    /// response it returned (see [`Self::id_source`])
+    pub fn acknowledge(ctx: &egui::Context, id: Id) {
+        ctx.data_mut(|d| PersistedState::get(d, id).alarm_latched = false);
+    }
+
+    /// Mark a target value, like the setpoint of a thermostat, with a triangle pointing at the
+    /// arc from the inside
+    pub fn target(mut self, target: impl emath::Numeric) -> Self {
+        self.target = Some(target.to_f64());
+        self
+    }
+
+    /// Shade the arc between the [`Self::target`] and the value, in the first color while the
+    /// value is below the target and in the second while it is above, so that how far the value
+    /// is off is as visible as where it is. Translucent colors let the arc show through, like
+    /// `Color32::RED.gamma_multiply(0.4)`.
+    pub fn deviation_band(mut self, below: Color32, above: Color32) -> Self {
+        self.deviation_band = Some((below, above));
+        self
+    }
+
+    /// Shade the arc between the target and the shown value, see [`Self::deviation_band`]
+    fn paint_deviation_band(&self, canvas: &mut Canvas, rect: Rect) {
+        let (Some(target), Some((below, above))) = (self.target, self.deviation_band) else {
+            return;
+        };
+        let target = self.clamp_to_range(target);
+        let value = self.clamp_to_range(self.shown_value);
+        let (from, to, color) = if value < target {
+            (value, target, below)
+        } else {
+            (target, value, above)
+        };
+        if from >= to {
+            return;
+        }
+        canvas.add(Shape::line(
+            self.arc(
+                rect,
+                self.value_to_angle(to),
+                self.value_to_angle(from),
+                self.radius() - self.thickness() / 2.0,
+            ),
+            Stroke {
+                width: self.thickness(),
+                color,
+            },
+        ));
+    }
+
+    /// Mark the given value on the scale, for limits, historical references, forecasts or notes.
+    /// Any number of markers can be added, and ones outside of the range stop at the ends of the
+    /// arc.
+    pub fn marker(mut self, value: impl emath::Numeric, style: MarkerStyle) -> Self {
+        self.markers.push((value.to_f64(), style));
+        self
+    }
+
+    fn paint_markers(&self, canvas: &mut Canvas, rect: Rect) {
+        for (value, style) in &self.markers {
+            match style {
+                MarkerStyle::Triangle(color) => self.paint_target(canvas, rect, *value, *color),
+                MarkerStyle::Line(color) => {
+                    let width = self
+                        .round_to_pixels(self.thickness() / 6.0)
+                        .max(self.line_width());
+                    self.paint_marker(canvas, rect, *value, width, *color);
+                }
+                MarkerStyle::Dot(color) => {
+                    let angle = self.value_to_angle(self.clamp_to_range(*value));
+                    let radius = self.radius() - self.thickness() / 2.0;
+                    let center = Pos2 {
+                        x: self.x_f(rect, angle, radius),
+                        y: self.y_f(rect, angle, radius),
+                    };
+                    canvas.circle(center, self.thickness() / 4.0, *color, Stroke::NONE);
+                }
+                MarkerStyle::Label(text, color) => {
+                    let angle = self.value_to_angle(self.clamp_to_range(*value));
+                    let outer = self.radius() - self.thickness() * 1.1;
+                    let inner = self.radius() - self.thickness() * 1.5;
+                    let at = |radius| Pos2 {
+                        x: self.x_f(rect, angle, radius),
+                        y: self.y_f(rect, angle, radius),
+                    };
+                    canvas.line_segment(
+                        [at(outer), at(inner)],
+                        Stroke {
+                            width: self.line_width(),
+                            color: *color,
+                        },
+                    );
+                    // anchored on the side facing the tick, so the text runs towards the center
+                    let (sin, cos) = (angle as f32).to_radians().sin_cos();
+                    let anchor = Align2([
+                        if cos > 0.3 {
+                            egui::Align::Max
+                        } else if cos < -0.3 {
+                            egui::Align::Min
+                        } else {
+                            egui::Align::Center
+                        },
+                        if sin > 0.3 {
+                            egui::Align::Min
+                        } else if sin < -0.3 {
+                            egui::Align::Max
+                        } else {
+                            egui::Align::Center
+                        },
+                    ]);
+                    canvas.text(
+                        at(inner - self.thickness() * 0.2),
+                        anchor,
+                        text,
+                        FontId::monospace(self.inner_width() / 20.0),
+                        *color,
+                    );
+                }
+            }
+        }
+    }
+
+    /// Mark the given value with a bold line across the arc, the classic "do not exceed" marking.
+    /// It is drawn in [`GaugeStyle::redline_color`], which defaults to the error color of the
+    /// egui visuals, and works alongside zones like a critical zone starting at the same value.
+    pub fn redline(mut self, redline: impl emath::Numeric) -> Self {
+        self.redline = Some(redline.to_f64());
+        self
+    }
+
+    /// Show a second value on the same gauge, like the outdoor temperature next to the indoor
+    /// one, with its own indicator in the given color. Where the second value is past the first,
+    /// the arc between them is filled in with the given color too.
+    pub fn second_value(mut self, value: impl emath::Numeric, color: Color32) -> Self {
+        self.second_value = Some((value.to_f64(), color));
+        self
+    }
+
+    /// Add a segment with the given label, value and color, stacked on top of the segments added
+    /// before it from the start of the range, like a stacked bar bent around the dial. Use this
+    /// for the parts of a total, like the user, system and I/O wait time of a CPU, with the total
+    /// as the value of the gauge. Negative values are treated as 0. The [`Self::legend`] lists
+    /// the segments after the values.
+    pub fn segment(
+        mut self,
+        label: impl Into<String>,
+        value: impl emath::Numeric,
+        color: Color32,
+    ) -> Self {
+        self.segments
+            .push((label.into(), value.to_f64().max(0.0), color));
+        self
+    }
+
+    /// Paint the segments over the value arc, each from where the one before it ends
+    fn paint_segments(&self, canvas: &mut Canvas, rect: Rect) {
+        let ends: Vec<_> = self
+            .segments
+            .iter()
+            .scan(self.min_value, |end, (_, value, color)| {
+                *end += value;
+                Some((*end, *color))
+            })
+            .collect();
+        // each arc runs from the start of the dial, so the later ones go underneath
+        for &(end, color) in ends.iter().rev() {
+            let angle = self.value_to_angle(self.clamp_to_range(end));
+            self.paint_arc(canvas, rect, angle, color);
+        }
+    }
+
+    /// Small text in the opening at the bottom of the dial, between the ends of the arc, where
+    /// real instruments print the units or the maker's name. Its font and color come from
+    /// [`GaugeStyle::skirt_font`] and [`GaugeStyle::skirt_color`]. Only three quarter dials have
+    /// an opening, and compact gauges leave it out.
+    pub fn skirt_text(mut self, text: impl Into<String>) -> Self {
+        self.skirt_text = text.into();
+        self
+    }
+
+    fn write_skirt_text(&self, canvas: &mut Canvas, rect: Rect, text_color: Color32) {
+        if self.skirt_text.is_empty() || self.dial != Dial::ThreeQuarter || self.is_compact() {
+            return;
+        }
+        let font = self
+            .style
+            .skirt_font
+            .clone()
+            .unwrap_or(FontId::monospace(self.inner_width() / 18.0));
+        let color = self.style.skirt_color.unwrap_or(text_color);
+        // the ends of the arc are at 45° below the horizontal, so the opening is level with them
+        let center = self.center(rect);
+        let max_width = self.radius() * 2.0 * FRAC_1_SQRT_2 - self.thickness() * 2.0;
+        let galley = canvas.layout_elided(self.skirt_text.clone(), font, color, max_width);
+        let pos = Pos2 {
+            x: center.x,
+            y: center.y + self.radius() * FRAC_1_SQRT_2 * self.aspect_ratio,
+        } - galley.size() / 2.0;
+        canvas.galley(pos, galley, color);
+    }
+
+    /// Show a row under the gauge which tells what the colors mean, with the given label for the
+    /// value and the label for the [`Self::second_value`], if there is one
+    pub fn legend(mut self, label: impl Into<String>, second_label: impl Into<String>) -> Self {
+        self.legend = Some((label.into(), second_label.into()));
+        self
+    }
+
+    /// Show a running total, like the distance travelled under a speedometer, as a box of digits
+    /// under the value
+    pub fn odometer(mut self, total: impl emath::Numeric) -> Self {
+        self.odometer = Some(total.to_f64());
+        self
+    }
+
+    /// Leave a trail behind the indicator of its last `len` positions, fading out with age
+    pub fn trail(mut self, len: usize) -> Self {
+        self.trail = Some(len);
+        self
+    }
+
+    /// Link the gauge to every other gauge with the same group, so that hovering over any of them
+    /// marks the value under the pointer on all of them. Useful for instruments showing the same
+    /// quantity at different scales, where values outside of a gauge's range aren't marked on it.
+    pub fn link_group(mut self, group: impl std::hash::Hash) -> Self {
+        self.link_group = Some(Id::new(group));
+        self
+    }
+
+    /// Share the value under the pointer with the link group when hovered, and pick up the value
+    /// hovered on another gauge of the group otherwise
+    fn update_link_group(&mut self, ui: &Ui, rect: Rect, response: &Response) {
+        let Some(group) = self.link_group else {
+            return;
+        };
+        if !self.has_valid_range() {
+            return;
+        }
+        let frame = ui.ctx().frame_nr();
+        if let Some(pos) = response.hover_pos() {
+            let hover = LinkedHover {
+                value: self.pos_to_value(rect, pos),
+                gauge: response.id,
+                frame,
+            };
+            ui.data_mut(|d| d.insert_temp(group, hover));
+        }
+        let hover = ui
+            .data(|d| d.get_temp::<LinkedHover>(group))
+            .filter(|hover| hover.frame + 1 >= frame);
+        if hover.is_some_and(|hover| hover.gauge == response.id && hover.frame < frame) {
+            // gauges painted before this one still mark the value from the last frame, until the
+            // next frame shows that this one isn't hovered anymore
+            ui.ctx().request_repaint();
+        }
+        self.linked_hover = hover
+            .map(|hover| hover.value)
+            .filter(|&value| (self.min_value..=self.max_value).contains(&value));
+    }
+
+    /// Identify the gauge by the given source (combined with the id of the parent `Ui`) rather
+    /// than by its position in the layout. Runtime state like the animation, peak and trail is
+    /// remembered per id, so gauges which move around or would otherwise collide need this.
+    pub fn id_source(mut self, id_source: impl std::hash::Hash) -> Self {
+        self.id_source = Some(Id::new(id_source));
+        self
+    }
+
+    /// The same as [`Self::id_source`], under the name newer versions of egui use
+    pub fn id_salt(self, id_salt: impl std::hash::Hash) -> Self {
+        self.id_source(id_salt)
+    }
+
+    /// Use exactly the given id for the gauge, without combining it with the id of the parent
+    /// `Ui`, so that the app can address its state with functions like [`Self::acknowledge`]
+    /// without keeping the response around. The id has to be unique across the whole app.
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    /// Mirror the gauge for right to left languages, so that the scale runs counter-clockwise from
+    /// the minimum on the right. Text is still read the normal way, and labels are anchored on the
+    /// mirrored side. By default gauges are mirrored when the layout they are in prefers right to
+    /// left, like other widgets in that layout.
+    pub fn right_to_left(mut self, right_to_left: bool) -> Self {
+        self.right_to_left = Some(right_to_left);
+        self
+    }
+
+    fn is_mirrored(&self) -> bool {
+        self.right_to_left.unwrap_or(self.layout_right_to_left)
+    }
+
+    /// Draw an overlay for working out layout problems: the rect allocated for the gauge, the rect
+    /// the arc is laid out in, the center of the dial, the angle of the indicator and the bounds of
+    /// every shape. It is drawn in [`GaugeStyle::debug_color`].
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Draw the gauge with a different shape of dial
+    pub fn dial(mut self, dial: Dial) -> Self {
+        self.dial = dial;
+        self
+    }
+
+    /// Scale the gauge to fill the width available in the layout, instead of using the size it
+    /// was created with
+    pub fn fit_to_width(mut self) -> Self {
+        self.fit = Some(Fit::Width);
+        self
+    }
+
+    /// Scale the gauge to be as big as fits in the space available in the layout, instead of
+    /// using the size it was created with
+    pub fn fit_to_available_size(mut self) -> Self {
+        self.fit = Some(Fit::AvailableSize);
+        self
+    }
+
+    /// Stretch the dial into an ellipse which is the given ratio as tall as it is wide, so that the
+    /// gauge fills a space which isn't square. Only the height of the gauge changes.
+    pub fn aspect_ratio(mut self, aspect_ratio: f32) -> Self {
+        self.aspect_ratio = aspect_ratio;
+        self
+    }
+
+    /// Whether to use the compact layout, which only labels the ends of the scale and shows
+    /// nothing but the value in the middle, all in bigger fonts. By default gauges smaller than
+    /// 100 points use it, since the normal layout gets unreadable.
+    pub fn compact(mut self, compact: bool) -> Self {
+        self.compact = Some(compact);
+        self
+    }
+
+    /// Only keep repainting for animations, springs and smoothing while the shown value is more
+    /// than the given precision away from the value, since smaller movements can't be seen
+    pub fn repaint_precision(mut self, precision: f64) -> Self {
+        self.repaint_precision = Some(precision);
+        self
+    }
+
+    /// Trade smoothness for less work, for embedded and battery powered devices: arcs are drawn
+    /// with fewer points, and animations, springs and smoothing repaint at about 20 frames per
+    /// second rather than every frame
+    pub fn low_overhead(mut self, low_overhead: bool) -> Self {
+        self.low_overhead = low_overhead;
+        self
+    }
+
+    /// While the value is NaN or infinite, like during a sensor dropout, keep showing the last
+    /// valid value faded out, so that it is clear that the reading is stale. Without it, the
+    /// gauge shows an empty arc.
+    pub fn stale_indicator(mut self, stale_indicator: bool) -> Self {
+        self.stale_indicator = stale_indicator;
+        self
+    }
+
+    /// Emphasize zero with a tick of its own, for ranges with negative and positive values like
+    /// temperatures or a balance from left to right
+    pub fn zero_tick(mut self, zero_tick: bool) -> Self {
+        self.labels.zero_tick = zero_tick;
+        self
+    }
+
+    /// How to show values outside of the range. By default the indicator just stops at the end of
+    /// the arc.
+    pub fn overflow(mut self, overflow: OverflowBehavior) -> Self {
+        self.overflow = overflow;
+        self
+    }
+
+    /// Whether a gauge showing the previous value needs to be repainted to show the new one,
+    /// when values are shown to the given precision. Apps receiving values in the background can
+    /// use this to only request a repaint when the shown reading would actually change.
+    pub fn needs_repaint(previous: f64, value: f64, precision: f64) -> bool {
+        if precision > 0.0 {
+            (previous / precision).round() != (value / precision).round()
+        } else {
+            previous != value
+        }
+    }
+
+    /// Filter the value before showing it, so that a noisy value doesn't make the indicator
+    /// vibrate. The value given to the gauge is left untouched.
+    pub fn smoothing(mut self, smoothing: Smoothing) -> Self {
+        self.smoothing = Some(smoothing);
+        self
+    }
+
+    /// Tell the gauge when its value was sampled, for values which are updated less often than
+    /// the app is painted, like a sensor publishing twice a second. The gauge remembers the last
+    /// two samples and moves the indicator between them the given way, repainting until it
+    /// catches up. The same sample can be given for as many frames as it is the latest one.
+    pub fn sampled_at(mut self, time: Instant, interpolation: Interpolation) -> Self {
+        self.sampled_at = Some((time, interpolation));
+        self
+    }
+
+    /// Change the colors and proportions of the gauge. Anything the style leaves as `None` comes
+    /// from the [`GaugeTheme`] installed in the context.
+    pub fn style(mut self, style: GaugeStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Dim all the colors of the gauge by the given factor, from 0 for black to 1 for its normal
+    /// colors, for night modes. This takes precedence over [`GaugeStyle::brightness`], which
+    /// dims every gauge when set on the [`GaugeTheme`].
+    pub fn brightness(mut self, brightness: f32) -> Self {
+        self.brightness = Some(brightness);
+        self
+    }
+
+    fn is_compact(&self) -> bool {
+        self.compact.unwrap_or(self.size < COMPACT_SIZE)
+    }
+
+    /// The size which makes the gauge fit into the available space
+    fn fitted_size(&mut self, available: Vec2, fit: Fit) -> f32 {
+        // the allocated size is proportional to the size, whatever the dial
+        let size = self.size;
+        self.size = 1.0;
+        let per_size = self.desired_size();
+        self.size = size;
+        let size = match fit {
+            Fit::Width => available.x / per_size.x,
+            Fit::AvailableSize => (available.x / per_size.x).min(available.y / per_size.y),
+        };
+        if size.is_finite() {
+            size.max(0.0)
+        } else {
+            self.size
+        }
+    }
+
+    fn inner_width(&self) -> f32 {
+        self.size - self.text_clearance() * 2.0
+    }
+
+    fn text_clearance(&self) -> f32 {
+        // without labels around the outside, only the zone bands need room past the arc
+        if self.dense {
+            return self.size / 30.0;
+        }
+        self.size / 10.0
+    }
+
+    fn x_f(&self, rect: Rect, angle: i32, radius: f32) -> f32 {
+        self.center(rect).x + (angle as f32 * PI / 180.0).cos() * radius
+    }
+
+    fn y_f(&self, rect: Rect, angle: i32, radius: f32) -> f32 {
+        self.center(rect).y - (angle as f32 * PI / 180.0).sin() * radius * self.aspect_ratio
+    }
+
+    /// Points along the arc at the given radius from one angle to the other, spaced for the arc
+    /// to look smooth at its size in pixels. The arc is built from Bézier curves, except in
+    /// [`Self::low_overhead`] mode, where it falls back to coarser evenly spaced points.
+    fn arc(&self, rect: Rect, from: i32, to: i32, radius: f32) -> Vec<Pos2> {
+        let center = self.center(rect);
+        if !self.low_overhead {
+            return bezier_arc_points(
+                center,
+                radius,
+                self.aspect_ratio,
+                from as f32,
+                to as f32,
+                ARC_TOLERANCE / self.pixels_per_point,
+            );
+        }
+
+        let aspect_ratio = self.aspect_ratio;
+        let pixels = radius * self.pixels_per_point * aspect_ratio.max(1.0);
+        let step = arc_step(pixels / LOW_OVERHEAD_COARSENESS);
+        let steps = ((to - from).abs() as f32 / step).ceil().max(1.0) as usize;
+        (0..=steps)
+            .map(|i| {
+                let angle =
+                    (from as f32 + (to - from) as f32 * i as f32 / steps as f32).to_radians();
+                Pos2 {
+                    x: center.x + angle.cos() * radius,
+                    y: center.y - angle.sin() * radius * aspect_ratio,
+                }
+            })
+            .collect()
+    }
+
+    /// Round the given length in points to a whole number of physical pixels, so that edges are
+    /// crisp
+    fn round_to_pixels(&self, points: f32) -> f32 {
+        (points * self.pixels_per_point).round() / self.pixels_per_point
+    }
+
+    /// The width of thin lines, which is about a point but always a whole number of pixels
+    fn line_width(&self) -> f32 {
+        let width = if self.is_high_contrast() { 2.0 } else { 1.0 };
+        self.round_to_pixels(width).max(1.0 / self.pixels_per_point)
+    }
+
+    /// The brightness of the gauge or its style, limited to what can be shown
+    fn effective_brightness(&self) -> f32 {
+        self.brightness
+            .or(self.style.brightness)
+            .unwrap_or(1.0)
+            .clamp(0.0, 1.0)
+    }
+
+    fn is_high_contrast(&self) -> bool {
+        self.style.high_contrast.unwrap_or(false)
+    }
+
+    /// The smallest font labels are shrunk to in order to fit
+    fn min_font_size(&self) -> f32 {
+        if self.is_high_contrast() {
+            MIN_LABEL_FONT_SIZE * 1.5
+        } else {
+            MIN_LABEL_FONT_SIZE
+        }
+    }
+
+    /// The radius before rounding to pixels, which the allocated size is proportional to
+    fn exact_radius(&self) -> f32 {
+        self.inner_width() / 2.0
+    }
+
+    fn radius(&self) -> f32 {
+        self.round_to_pixels(self.exact_radius())
+    }
+
+    fn thickness(&self) -> f32 {
+        let default = if self.is_high_contrast() {
+            1.0 / 10.0
+        } else if self.dense {
+            1.0 / 20.0
+        } else {
+            1.0 / 15.0
+        };
+        let thickness = self.style.thickness.unwrap_or(default);
+        self.round_to_pixels(self.inner_width() * thickness)
+            .max(1.0 / self.pixels_per_point)
+    }
+
+    /// The size the gauge takes up in the layout, including its labels
+    pub fn desired_size(&self) -> Vec2 {
+        self.dial_size() + egui::vec2(0.0, self.legend_height())
+    }
+
+    /// The size of the dial and the labels around it, without the legend
+    fn dial_size(&self) -> Vec2 {
+        match self.dial {
+            Dial::ThreeQuarter => egui::vec2(
+                self.size,
+                self.exact_radius() * 2.0 * self.aspect_ratio + self.text_clearance() * 2.0,
+            ),
+            Dial::Half => egui::vec2(
+                self.size,
+                self.exact_radius() * self.aspect_ratio + self.text_clearance() * 2.0,
+            ),
+            Dial::Quarter => egui::vec2(
+                self.exact_radius() + self.text_clearance() * 2.0,
+                self.exact_radius() * self.aspect_ratio + self.text_clearance() * 2.0,
+            ),
+        }
+    }
+
+    fn center(&self, rect: Rect) -> Pos2 {
+        let center = match self.dial {
+            Dial::ThreeQuarter => Pos2 {
+                x: rect.left() + rect.width() / 2.0,
+                y: rect.bottom() - rect.height() / 2.0,
+            },
+            Dial::Half => Pos2 {
+                x: rect.left() + rect.width() / 2.0,
+                y: rect.bottom(),
+            },
+            Dial::Quarter => rect.right_bottom(),
+        };
+        Pos2 {
+            x: self.round_to_pixels(center.x),
+            y: self.round_to_pixels(center.y),
+        }
+    }
+
+    /// Where the value, sparkline and text are laid out around, which is raised up into the arc
+    /// when the dial has no room below its center
+    fn content_center(&self, rect: Rect) -> Pos2 {
+        match self.dial {
+            Dial::ThreeQuarter => self.center(rect),
+            Dial::Half => {
+                self.center(rect) - egui::vec2(0.0, self.radius() * 0.45 * self.aspect_ratio)
+            }
+            Dial::Quarter => {
+                self.center(rect)
+                    - egui::vec2(
+                        self.radius() * 0.4,
+                        self.radius() * 0.45 * self.aspect_ratio,
+                    )
+            }
+        }
+    }
+
+    /// The width the value, sparkline and text in the middle are sized relative to, which shrinks
+    /// along with the dial when it is squashed
+    fn content_width(&self) -> f32 {
+        self.inner_width() * self.dial.content_scale() * self.aspect_ratio.min(1.0)
+    }
+
+    /// Where the given position is compared to the center, undoing the stretching of the dial
+    fn offset_from_center(&self, rect: Rect, pos: Pos2) -> Vec2 {
+        // the dial is painted unmirrored and flipped over the middle, so flip the position back
+        let pos = if self.is_mirrored() {
+            Pos2 {
+                x: 2.0 * rect.center().x - pos.x,
+                ..pos
+            }
+        } else {
+            pos
+        };
+        let offset = pos - self.center(rect);
+        egui::vec2(offset.x, offset.y / self.aspect_ratio)
+    }
+
+    /// The angle of the given value on the arc, where values outside of the range stop at the
+    /// ends of the arc
+    fn value_to_angle(&self, v: f64) -> i32 {
+        let sweep = self.dial.sweep() as f64;
+        let fraction = ((v - self.min_value) / (self.max_value - self.min_value)).clamp(0.0, 1.0);
+        ((sweep - fraction * sweep) + self.dial.end_angle() as f64) as i32
+    }
+
+    fn angle(&self) -> i32 {
+        self.value_to_angle(self.shown_value)
+    }
+
+    /// The value corresponding to the point on the arc nearest to the given position. Positions in
+    /// the gap at the bottom of the gauge snap to whichever end is closer.
+    fn pos_to_value(&self, rect: Rect, pos: Pos2) -> f64 {
+        let full_sweep = self.dial.sweep() as f32;
+        let sweep = self.pos_to_sweep(rect, pos);
+        let sweep = if sweep <= full_sweep {
+            sweep
+        } else if sweep < full_sweep + (360.0 - full_sweep) / 2.0 {
+            full_sweep
+        } else {
+            0.0
+        };
+        self.min_value + (sweep / full_sweep) as f64 * (self.max_value - self.min_value)
+    }
+
+    /// How many degrees clockwise from the start of the arc the given position is, from 0 to 360
+    fn pos_to_sweep(&self, rect: Rect, pos: Pos2) -> f32 {
+        let Vec2 { x, y } = self.offset_from_center(rect, pos);
+        (self.dial.start_angle() as f32 - (-y).atan2(x).to_degrees()).rem_euclid(360.0)
+    }
+
+    /// Whether the given position lies on the ring of the arc
+    fn is_on_arc(&self, rect: Rect, pos: Pos2) -> bool {
+        let distance = self.offset_from_center(rect, pos).length();
+        distance >= self.radius() - self.thickness()
+            && distance <= self.radius()
+            && self.pos_to_sweep(rect, pos) <= self.dial.sweep() as f32
+    }
+
+    /// Snap the value to the step, which for gauges of integers is 1 unless told otherwise
+    fn snap_to_step(&self, value: f64) -> f64 {
+        match self.step.or(self.integral.then_some(1.0)) {
+            Some(step) => self
+                .clamp_to_range(self.min_value + ((value - self.min_value) / step).round() * step),
+            None => value,
+        }
+    }
+
+    fn edit_value(&mut self, value: f64, response: &mut Response) {
+        let value = self.snap_to_step(value);
+        if value != self.value {
+            self.value = match &mut self.binding {
+                // read it back, since the bound type might not be able to hold it exactly
+                Some(Binding(binding)) => binding(Some(value)),
+                None => value,
+            };
+            response.mark_changed();
+        }
+    }
+
+    fn clamp_to_range(&self, value: f64) -> f64 {
+        if self.has_valid_range() {
+            value.clamp(self.min_value, self.max_value)
+        } else {
+            value
+        }
+    }
+
+    /// Whether the range covers any values, so that values can be placed on the arc. A gauge with
+    /// an empty or infinite range is shown greyed out instead.
+    fn has_valid_range(&self) -> bool {
+        is_valid_range(self.min_value, self.max_value)
+    }
+
+    fn step(&self) -> f64 {
+        let default = (self.max_value - self.min_value) / 100.0;
+        self.scroll_step.or(self.step).unwrap_or(if self.integral {
+            default.round().max(1.0)
+        } else {
+            default
+        })
+    }
+
+    /// The value as text, which for gauges of integers is always a whole number, even when the
+    /// value is smoothed or was converted from a float
+    fn value_text(&self) -> String {
+        if self.integral && self.value.is_finite() {
+            // adding zero turns negative zero into positive zero
+            (self.value.round() + 0.0).to_string()
+        } else {
+            self.value.to_string()
+        }
+    }
+
+    fn handle_keyboard(&mut self, ui: &Ui, response: &mut Response) {
+        ui.memory_mut(|m| {
+            m.set_focus_lock_filter(
+                response.id,
+                EventFilter {
+                    horizontal_arrows: true,
+                    vertical_arrows: true,
+                    ..Default::default()
+                },
+            )
+        });
+
+        // the arrow pointing the way the needle moves as the value goes up
+        let (forward, back) = if self.is_mirrored() {
+            (Key::ArrowLeft, Key::ArrowRight)
+        } else {
+            (Key::ArrowRight, Key::ArrowLeft)
+        };
+        let (steps, pages, home, end) = ui.input(|i| {
+            let steps = i.num_presses(Key::ArrowUp) + i.num_presses(forward);
+            let steps = steps as f64 - (i.num_presses(Key::ArrowDown) + i.num_presses(back)) as f64;
+            let pages = i.num_presses(Key::PageUp) as f64 - i.num_presses(Key::PageDown) as f64;
+            (
+                steps,
+                pages,
+                i.key_pressed(Key::Home),
+                i.key_pressed(Key::End),
+            )
+        });
+
+        let page = (self.max_value - self.min_value) / 10.0;
+        if steps != 0.0 || pages != 0.0 {
+            let value = self.clamp_to_range(self.value + steps * self.step() + pages * page);
+            self.edit_value(value, response);
+        }
+        if home {
+            self.edit_value(self.min_value, response);
+        }
+        if end {
+            self.edit_value(self.max_value, response);
+        }
+    }
+
+    fn handle_drag(&mut self, ui: &Ui, rect: Rect, response: &mut Response) {
+        let Some(pos) = response.interact_pointer_pos() else {
+            return;
+        };
+        let fine = ui.input(|i| i.modifiers.shift_only());
+        let sensitivity = if fine {
+            self.drag_sensitivity * FINE_ADJUST
+        } else {
+            self.drag_sensitivity
+        };
+
+        let previous = pos - response.drag_delta();
+        let delta = (self.pos_to_sweep(rect, pos) - self.pos_to_sweep(rect, previous) + 180.0)
+            .rem_euclid(360.0)
+            - 180.0;
+
+        // the value is accumulated unsnapped, so that small movements still add up to a step
+        let drag_value = ui.data_mut(|d| {
+            let state = d.get_temp_mut_or_default::<GaugeState>(response.id);
+            if response.drag_started() || state.drag_value.is_none() {
+                // when the indicator follows the pointer, start from wherever the pointer is
+                state.drag_value = Some(if sensitivity == 1.0 {
+                    self.pos_to_value(rect, previous)
+                } else {
+                    self.value
+                });
+            }
+            let drag_value = state.drag_value.get_or_insert(self.value);
+            *drag_value += delta as f64 / self.dial.sweep() as f64
+                * (self.max_value - self.min_value)
+                * sensitivity;
+            *drag_value
+        });
+        self.edit_value(self.clamp_to_range(drag_value), response);
+    }
+
+    fn handle_interaction(&mut self, ui: &Ui, rect: Rect, response: &mut Response) {
+        if self.binding.is_none() || !self.has_valid_range() {
+            return;
+        }
+
+        if response.clicked() || response.drag_started() {
+            response.request_focus();
+        }
+        if response.has_focus() {
+            self.handle_keyboard(ui, response);
+        }
+
+        if response.hovered() {
+            let scroll = ui.input(|i| i.raw_scroll_delta);
+            let delta = scroll.x + scroll.y;
+            if delta != 0.0 {
+                // don't also scroll whatever the gauge is inside of
+                ui.input_mut(|i| {
+                    i.raw_scroll_delta = Vec2::ZERO;
+                    i.smooth_scroll_delta = Vec2::ZERO;
+                });
+                let value = self.clamp_to_range(self.value + self.step() * delta.signum() as f64);
+                self.edit_value(value, response);
+            }
+        }
+
+        if response.dragged() || response.drag_stopped() {
+            self.handle_drag(ui, rect, response);
+        } else if response.double_clicked() {
+            if let Some(value) = self.default_value {
+                self.edit_value(value, response);
+            }
+        } else if response.clicked() {
+            if let Some(pos) = response
+                .interact_pointer_pos()
+                .filter(|&pos| self.is_on_arc(rect, pos))
+            {
+                self.edit_value(self.pos_to_value(rect, pos), response);
+            }
+        }
+    }
+
+    /// The zone the value is currently inside of, if any
+    fn current_zone(&self) -> Option<&Zone> {
+        zone::zone_index_at(&self.zones, self.value, self.previous_zone).map(|i| &self.zones[i])
+    }
+
+    /// Whether the value is above (`Some(true)`) or below (`Some(false)`) the range
+    fn overflow_direction(&self) -> Option<bool> {
+        if self.value > self.max_value {
+            Some(true)
+        } else if self.value < self.min_value {
+            Some(false)
+        } else {
+            None
+        }
+    }
+
+    /// The color of the value, which the palette of the style replaces
+    fn accent_color(&self) -> Color32 {
+        self.style
+            .palette
+            .map_or(self.color, |palette| palette.accent)
+    }
+
+    fn zone_color(&self, zone: &Zone) -> Color32 {
+        self.style
+            .palette
+            .map_or(zone.color, |palette| palette.zone_color(zone.severity))
+    }
+
+    /// The color of the value arc, which depends on the zone and blinks or pulses during an alarm
+    fn arc_color(&self, canvas: &Canvas) -> Color32 {
+        let zone = self.current_zone();
+        let mut color = match self.overflow {
+            OverflowBehavior::Saturate(color) if self.overflow_direction().is_some() => color,
+            _ => zone.map_or(self.accent_color(), |zone| self.zone_color(zone)),
+        };
+        let severity = zone.map_or(Severity::Normal, |zone| zone.severity);
+        let time = canvas.ctx().input(|i| i.time);
+
+        if let Some(rate) = self
+            .blink_rate
+            .filter(|&rate| rate > 0.0 && severity > Severity::Normal)
+        {
+            // on for the first half of every period, off for the second
+            let phase = (time * rate as f64).fract();
+            let on = phase < 0.5;
+            let until_toggle = (if on { 0.5 } else { 1.0 } - phase) / rate as f64;
+            canvas
+                .ctx()
+                .request_repaint_after(Duration::from_secs_f64(until_toggle));
+            if !on {
+                color = color.gamma_multiply(0.25);
+            }
+        }
+
+        if let Some(rate) = self
+            .pulse_rate
+            .filter(|&rate| rate > 0.0 && severity == Severity::Critical)
+        {
+            let phase = (time * rate as f64).fract() as f32;
+            color = color.gamma_multiply(0.65 + 0.35 * (phase * 2.0 * PI).cos());
+            canvas.ctx().request_repaint();
+        }
+        color
+    }
+
+    /// The color of the value in the center, which fades from the color of the gauge back to the
+    /// text color after a large change
+    fn value_color(&self, canvas: &Canvas, state: &GaugeState, text_color: Color32) -> Color32 {
+        let Some(flash_time) = state.flash_time else {
+            return text_color;
+        };
+        let elapsed = canvas.ctx().input(|i| i.time) - flash_time;
+        if elapsed < FLASH_DURATION {
+            mix(
+                self.accent_color(),
+                text_color,
+                (elapsed / FLASH_DURATION) as f32,
+            )
+        } else {
+            text_color
+        }
+    }
+
+    fn rate_to_angle(&self, rate: f64, full_scale: f64) -> i32 {
+        let middle = (self.dial.start_angle() + self.dial.end_angle()) as f64 / 2.0;
+        (middle - (rate / full_scale).clamp(-1.0, 1.0) * self.dial.sweep() as f64 / 2.0) as i32
+    }
+
+    /// The part of the rect allocated for the gauge which the arc is laid out in, leaving room for
+    /// the labels around it
+    fn arc_rect(&self, outer_rect: Rect) -> Rect {
+        self.geometry().arc_rect(outer_rect)
+    }
+
+    /// The dial as the gauge's size and style lay it out now
+    fn geometry(&self) -> DialGeometry {
+        DialGeometry {
+            radius: self.radius(),
+            thickness: self.thickness(),
+            text_clearance: self.text_clearance(),
+            legend_height: self.legend_height(),
+        }
+    }
+
+    /// The dial the gauge was last shown with, which can differ from its own size and style
+    /// when it fits itself to the available space or takes them from the theme
+    fn laid_out_geometry(&self) -> DialGeometry {
+        self.laid_out.unwrap_or_else(|| self.geometry())
+    }
+
+    fn legend_font_size(&self) -> f32 {
+        self.inner_width() / 15.0
+    }
+
+    /// The height of the row under the dial for the legend
+    fn legend_height(&self) -> f32 {
+        if self.legend.is_some() {
+            self.legend_font_size() * 1.8
+        } else {
+            0.0
+        }
+    }
+
+    /// A row at the bottom of the given rect with a swatch of color and a label for each value
+    fn paint_legend(&self, canvas: &mut Canvas, outer_rect: Rect, text_color: Color32) {
+        let Some((label, second_label)) = &self.legend else {
+            return;
+        };
+        let mut entries = vec![(label, self.accent_color())];
+        if let Some((_, color)) = self.second_value {
+            entries.push((second_label, color));
+        }
+        entries.extend(
+            self.segments
+                .iter()
+                .map(|(label, _, color)| (label, *color)),
+        );
+
+        let font_size = self.legend_font_size();
+        let swatch_size = font_size * 0.8;
+        let gap = font_size;
+        let galleys: Vec<_> = entries
+            .into_iter()
+            .map(|(label, color)| {
+                let galley = canvas.layout_no_wrap(
+                    label.clone(),
+                    FontId {
+                        size: font_size,
+                        family: FontFamily::Monospace,
+                    },
+                    text_color,
+                );
+                (galley, color)
+            })
+            .collect();
+        let width: f32 = galleys
+            .iter()
+            .map(|(galley, _)| swatch_size * 1.5 + galley.size().x)
+            .sum::<f32>()
+            + gap * (galleys.len() - 1) as f32;
+
+        let y = outer_rect.bottom() - self.legend_height() / 2.0;
+        let mut x = outer_rect.center().x - width / 2.0;
+        for (galley, color) in galleys {
+            let swatch = Rect::from_min_size(
+                Pos2 {
+                    x,
+                    y: y - swatch_size / 2.0,
+                },
+                egui::vec2(swatch_size, swatch_size),
+            );
+            canvas.add(Shape::rect_filled(swatch, swatch_size / 4.0, color));
+            x += swatch_size * 1.5;
+            let width = galley.size().x;
+            canvas.galley(
+                Pos2 {
+                    x,
+                    y: y - galley.size().y / 2.0,
+                },
+                galley,
+                text_color,
+            );
+            x += width + gap;
+        }
+    }
+
+    /// Paint the gauge. With the id of the gauge, the parts of the gauge which don't depend on
+    /// its value are kept in memory and reused for as long as they don't change.
+    fn paint(
+        &self,
+        canvas: &mut Canvas,
+        id: Option<Id>,
+        outer_rect: Rect,
+        state: &GaugeState,
+        persisted: &PersistedState,
+        has_focus: bool,
+    ) {
+        if !self.is_mirrored() && !self.debug && self.effective_brightness() == 1.0 {
+            self.paint_unmirrored(canvas, id, outer_rect, state, persisted, has_focus);
+            return;
+        }
+        let mut layer = canvas.fork();
+        self.paint_unmirrored(&mut layer, id, outer_rect, state, persisted, has_focus);
+        if self.effective_brightness() < 1.0 {
+            layer.dim(self.effective_brightness());
+        }
+        if self.debug {
+            self.paint_debug_overlay(&mut layer, outer_rect);
+        }
+        if self.is_mirrored() {
+            // painted the normal way around and flipped over, which keeps the cached shapes the
+            // same for both directions
+            layer.mirror(outer_rect.center().x);
+        }
+        canvas.extend(layer.into_shapes());
+    }
+
+    /// Outline the allocated rect, the rect of the arc and every shape painted so far, and mark
+    /// the center of the dial and the angle of the indicator
+    fn paint_debug_overlay(&self, canvas: &mut Canvas, outer_rect: Rect) {
+        let color = self
+            .style
+            .debug_color
+            .unwrap_or(Color32::from_rgb(255, 0, 255));
+        let thin = Stroke {
+            width: 1.0 / self.pixels_per_point,
+            color: color.gamma_multiply(0.5),
+        };
+        let stroke = Stroke { width: 1.0, color };
+
+        let bounds: Vec<Rect> = canvas
+            .shapes()
+            .iter()
+            .map(Shape::visual_bounding_rect)
+            .filter(|bounds| bounds.is_positive())
+            .collect();
+        for bounds in bounds {
+            canvas.rect_stroke(bounds, 0.0, thin);
+        }
+
+        let rect = self.arc_rect(outer_rect);
+        canvas.rect_stroke(outer_rect, 0.0, stroke);
+        canvas.rect_stroke(rect, 0.0, stroke);
+
+        let center = self.center(rect);
+        let arm = self.thickness();
+        canvas.line_segment(
+            [center - egui::vec2(arm, 0.0), center + egui::vec2(arm, 0.0)],
+            stroke,
+        );
+        canvas.line_segment(
+            [center - egui::vec2(0.0, arm), center + egui::vec2(0.0, arm)],
+            stroke,
+        );
+
+        let angle = self.angle();
+        let radius = self.radius();
+        let end = Pos2 {
+            x: self.x_f(rect, angle, radius),
+            y: self.y_f(rect, angle, radius),
+        };
+        canvas.line_segment([center, end], stroke);
+        canvas.text(
+            center + egui::vec2(arm, arm),
+            Align2::LEFT_TOP,
+            format!("{angle}°"),
+            FontId::monospace(self.inner_width() / 20.0),
+            color,
+        );
+    }
+
+    fn paint_unmirrored(
+        &self,
+        canvas: &mut Canvas,
+        id: Option<Id>,
+        outer_rect: Rect,
+        state: &GaugeState,
+        persisted: &PersistedState,
+        has_focus: bool,
+    ) {
+        let rect = self.arc_rect(outer_rect);
+
+        let visuals = &canvas.style().visuals.widgets.noninteractive;
+
+        let mut text_color = visuals.text_color();
+        let mut default_track_color = track_color(&canvas.style().visuals);
+        if self.is_high_contrast() {
+            let (background, foreground) = if canvas.style().visuals.dark_mode {
+                (Color32::BLACK, Color32::WHITE)
+            } else {
+                (Color32::WHITE, Color32::BLACK)
+            };
+            canvas.add(Shape::rect_filled(outer_rect, 0.0, background));
+            text_color = foreground;
+            // halfway, so that it stands out from the background as much as the value does
+            default_track_color = Color32::from_gray(128);
+        }
+        let text_color = self.style.text_color.unwrap_or(text_color);
+        let arc_bg_color = self.style.track_color.unwrap_or(default_track_color);
+        self.paint_legend(canvas, outer_rect, text_color);
+        if !self.has_valid_range() {
+            self.paint_invalid(canvas, rect, arc_bg_color, text_color);
+            return;
+        }
+        if !self.value.is_finite() {
+            self.paint_no_data(canvas, id, rect, state, arc_bg_color, text_color);
+            return;
+        }
+        let arc_color = self.arc_color(canvas);
+
+        match id {
+            Some(id) => self.paint_static_cached(canvas, id, rect, arc_bg_color, text_color),
+            None => self.paint_static(canvas, rect, arc_bg_color, text_color),
+        }
+        if let Some((value, color)) = self.second_value {
+            let angle = self.value_to_angle(self.clamp_to_range(value));
+            self.paint_arc(canvas, rect, angle, color);
+        }
+        self.paint_arc(canvas, rect, self.angle(), arc_color);
+        self.paint_segments(canvas, rect);
+        self.paint_deviation_band(canvas, rect);
+        // the start of the arc is covered by the first segment, when there is one
+        let start_color = self
+            .segments
+            .iter()
+            .find(|(_, value, _)| *value > 0.0)
+            .map_or(arc_color, |(_, _, color)| *color);
+        self.paint_end_caps(canvas, rect, start_color, arc_bg_color);
+        if self.trail.is_some() {
+            self.paint_trail(canvas, rect, &state.trail, arc_color);
+        }
+        if let Some(redline) = self.redline {
+            let color = self
+                .style
+                .redline_color
+                .unwrap_or(canvas.style().visuals.error_fg_color);
+            let width = self.round_to_pixels(self.thickness() / 3.0);
+            self.paint_marker(canvas, rect, redline, width, color);
+        }
+        self.paint_markers(canvas, rect);
+        if self.zone_edit.is_some() {
+            self.paint_zone_handles(canvas, rect, text_color);
+        }
+        if let Some(value) = self.linked_hover {
+            let width = self.round_to_pixels(self.thickness() / 4.0);
+            let color = canvas.style().visuals.selection.bg_fill;
+            self.paint_marker(canvas, rect, value, width, color);
+        }
+        if let Some((value, color)) = self.second_value {
+            let angle = self.value_to_angle(self.clamp_to_range(value));
+            self.paint_value_circle(canvas, rect, angle, color);
+        }
+        self.paint_value_circle(canvas, rect, self.angle(), arc_color);
+        // markers need to stand out against both the value arc and the background arc
+        let marker_color = canvas.style().visuals.extreme_bg_color;
+        if let Some(above) = self
+            .overflow_direction()
+            .filter(|_| self.overflow == OverflowBehavior::Indicate)
+        {
+            self.paint_overflow_indicator(canvas, rect, above, arc_color, marker_color);
+        }
+        if persisted.alarm_latched && self.latch_alarm {
+            self.paint_alarm_outline(canvas, rect);
+        }
+        if has_focus {
+            self.paint_focus_outline(canvas, rect);
+        }
+        if let Some(full_scale) = self.rate_of_change {
+            self.paint_rate_needle(canvas, rect, state.rate(), full_scale);
+        }
+        if let Some((peak, _)) = persisted.peak.filter(|_| self.peak_decay.is_some()) {
+            self.paint_marker(canvas, rect, peak, self.thickness() / 5.0, marker_color);
+        }
+        if let Some((low, high)) = persisted.watermarks.filter(|_| self.watermarks) {
+            self.paint_marker(canvas, rect, low, self.thickness() / 10.0, marker_color);
+            self.paint_marker(canvas, rect, high, self.thickness() / 10.0, marker_color);
+        }
+        if let Some(target) = self.target {
+            self.paint_target(canvas, rect, target, text_color);
+        }
+        let zone_text_color = self.current_zone().and_then(|zone| {
+            zone.text_color
+                .or(self.zone_text_color.then_some(self.zone_color(zone)))
+        });
+        let value_color = self.value_color(canvas, state, zone_text_color.unwrap_or(text_color));
+        self.write_center_value(canvas, rect, value_color);
+        if self.is_compact() {
+            return;
+        }
+
+        if let Some(statistics) = persisted.statistics.filter(|_| self.statistics) {
+            self.write_statistics(canvas, rect, statistics, text_color);
+        }
+        if self.sparkline.is_some() {
+            self.paint_sparkline(canvas, rect, &state.history);
+        }
+        if let Some(total) = self.odometer {
+            self.paint_odometer(canvas, rect, total, text_color);
+        }
+        if !self.text.is_empty() {
+            self.write_text(canvas, rect, text_color);
+        }
+    }
+
+    /// A greyed out gauge with a dash in place of the value, for ranges which values can't be
+    /// placed on
+    fn paint_invalid(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        arc_bg_color: Color32,
+        text_color: Color32,
+    ) {
+        let text_color = text_color.gamma_multiply(0.5);
+        self.paint_arc(
+            canvas,
+            rect,
+            self.dial.end_angle(),
+            arc_bg_color.gamma_multiply(0.5),
+        );
+        self.write_placeholder(canvas, rect, text_color);
+        if !self.is_compact() && !self.text.is_empty() {
+            self.write_text(canvas, rect, text_color);
+        }
+    }
+
+    /// An empty arc with a dash in place of the value, for values which are NaN or infinite. With
+    /// the [`Self::stale_indicator`], the last valid value is shown faded out.
+    fn paint_no_data(
+        &self,
+        canvas: &mut Canvas,
+        id: Option<Id>,
+        rect: Rect,
+        state: &GaugeState,
+        arc_bg_color: Color32,
+        text_color: Color32,
+    ) {
+        match id {
+            Some(id) => self.paint_static_cached(canvas, id, rect, arc_bg_color, text_color),
+            None => self.paint_static(canvas, rect, arc_bg_color, text_color),
+        }
+        if let Some(value) = state.last_valid_value.filter(|_| self.stale_indicator) {
+            let angle = self.value_to_angle(value);
+            let color = self.accent_color().gamma_multiply(0.3);
+            self.paint_arc(canvas, rect, angle, color);
+            self.paint_value_circle(canvas, rect, angle, color);
+        }
+        self.write_placeholder(canvas, rect, text_color);
+        if !self.is_compact() && !self.text.is_empty() {
+            self.write_text(canvas, rect, text_color);
+        }
+    }
+
+    /// A dash in place of the value in the center, for when there is no value to show
+    fn write_placeholder(&self, canvas: &mut Canvas, rect: Rect, text_color: Color32) {
+        canvas.text(
+            self.content_center(rect),
+            Align2::CENTER_CENTER,
+            "—".to_owned(),
+            self.center_value_font(),
+            text_color,
+        );
+    }
+
+    /// The parts of the gauge which only depend on its size, range and style: the background of
+    /// the arc and the labels around it
+    fn paint_static(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        arc_bg_color: Color32,
+        text_color: Color32,
+    ) {
+        self.paint_arc(canvas, rect, self.dial.end_angle(), arc_bg_color);
+        self.paint_zone_bands(canvas, rect);
+        if self.labels.has_zero_tick(self.min_value, self.max_value) {
+            self.paint_zero_tick(canvas, rect, text_color);
+        }
+        self.write_values_around_circle(canvas, rect, text_color);
+        self.write_skirt_text(canvas, rect, text_color);
+    }
+
+    /// The width of the bands showing the zones along the outside of the arc
+    fn zone_band_width(&self) -> f32 {
+        self.round_to_pixels(self.thickness() / 5.0)
+            .max(self.line_width())
+    }
+
+    /// A thin band along the outside of the arc in the color of each zone
+    fn paint_zone_bands(&self, canvas: &mut Canvas, rect: Rect) {
+        let width = self.zone_band_width();
+        let radius = self.radius() + width * 1.5;
+        for zone in &self.zones {
+            let start = self.clamp_to_range(*zone.range.start());
+            let end = self.clamp_to_range(*zone.range.end());
+            if start >= end {
+                continue;
+            }
+            let points = self.arc(
+                rect,
+                self.value_to_angle(end),
+                self.value_to_angle(start),
+                radius,
+            );
+            let stroke = Stroke {
+                width,
+                color: self.zone_color(zone),
+            };
+            let dash = match zone.severity {
+                _ if !self.style.zone_patterns.unwrap_or(false) => None,
+                Severity::Normal => None,
+                Severity::Warning => Some((width * 3.0, width * 1.5)),
+                Severity::Critical => Some((width, width)),
+            };
+            match dash {
+                Some((length, gap)) => {
+                    canvas.extend(Shape::dashed_line(&points, stroke, length, gap))
+                }
+                None => canvas.add(Shape::line(points, stroke)),
+            }
+        }
+    }
+
+    /// A tick on the inside of the arc where the value is zero
+    fn paint_zero_tick(&self, canvas: &mut Canvas, rect: Rect, color: Color32) {
+        let angle = self.value_to_angle(0.0);
+        let outer = self.radius() - self.thickness() * 1.15;
+        let inner = self.radius() - self.thickness() * 1.6;
+        canvas.line_segment(
+            [
+                Pos2 {
+                    x: self.x_f(rect, angle, inner),
+                    y: self.y_f(rect, angle, inner),
+                },
+                Pos2 {
+                    x: self.x_f(rect, angle, outer),
+                    y: self.y_f(rect, angle, outer),
+                },
+            ],
+            Stroke {
+                width: self.line_width() * 2.0,
+                color,
+            },
+        );
+    }
+
+    fn static_inputs(&self) -> StaticInputs<'_> {
+        StaticInputs {
+            size: Keyed([self.size, self.aspect_ratio, self.pixels_per_point]),
+            range: Keyed([self.min_value, self.max_value]),
+            dial: self.dial,
+            labels: self.labels,
+            zones: Keyed(self.zones.as_slice()),
+            style: Keyed(&self.style),
+            compact: self.is_compact(),
+            dense: self.dense,
+            low_overhead: self.low_overhead,
+            skirt_text: &self.skirt_text,
+            legend: &self.legend,
+        }
+    }
+
+    /// Paint the static parts of the gauge, reusing their shapes while the size, range and style
+    /// stay the same. Their text is checked against egui's fonts every frame, since the galleys
+    /// kept in the shapes are only good until egui rebuilds its font atlas.
+    fn paint_static_cached(
+        &self,
+        canvas: &mut Canvas,
+        id: Id,
+        rect: Rect,
+        arc_bg_color: Color32,
+        text_color: Color32,
+    ) {
+        let key = ShapeCache::key(&(
+            Keyed(rect),
+            self.static_inputs(),
+            [arc_bg_color, text_color],
+        ));
+        let shapes = ShapeCache::get_or_paint(canvas.ctx(), id.with("static"), key, || {
+            let mut layer = canvas.fork();
+            self.paint_static(&mut layer, rect, arc_bg_color, text_color);
+            layer.into_shapes()
+        });
+        canvas.extend(shapes.iter().cloned());
+    }
+
+    fn write_text(&self, canvas: &mut Canvas, rect: Rect, text_color: Color32) {
+        let center = self.content_center(rect);
+        let wrap_width = self.content_width() * 2.0 / 3.0;
+        let text = canvas.layout(
+            self.text.clone(),
+            FontId {
+                size: self.content_width() / 10.0,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+            wrap_width,
+        );
+        // make room for the statistics, sparkline and odometer between the value and the text
+        let mut offset = self.content_width() / 5.0 + self.statistics_height();
+        if self.sparkline.is_some() {
+            offset += self.content_width() * 0.08;
+        }
+        if self.odometer.is_some() {
+            offset += self.content_width() * 0.1;
+        }
+        canvas.galley(
+            Pos2 {
+                x: center.x - text.rect.width() / 2.0,
+                y: center.y + offset - text.rect.height() / 2.0,
+            },
+            text,
+            text_color,
+        );
+    }
+
+    /// The room taken up by the row of statistics under the value
+    fn statistics_height(&self) -> f32 {
+        if self.statistics {
+            self.content_width() * 0.08
+        } else {
+            0.0
+        }
+    }
+
+    fn write_statistics(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        statistics: Statistics,
+        text_color: Color32,
+    ) {
+        let format = |value| self.labels.format(value, self.min_value, self.max_value);
+        let center = self.content_center(rect);
+        canvas.text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.content_width() * 0.11,
+            },
+            Align2::CENTER_TOP,
+            format!(
+                "↓{} ~{} ↑{}",
+                format(statistics.min),
+                format(statistics.mean()),
+                format(statistics.max)
+            ),
+            FontId::monospace(self.content_width() * 0.06),
+            text_color,
+        );
+    }
+
+    fn paint_odometer(&self, canvas: &mut Canvas, rect: Rect, total: f64, text_color: Color32) {
+        let center = self.content_center(rect);
+        let top = if self.sparkline.is_some() {
+            center.y + self.content_width() * 0.21
+        } else {
+            center.y + self.content_width() * 0.11
+        } + self.statistics_height();
+        let digits = canvas.layout_no_wrap(
+            format!("{:06.0}", total.max(0.0)),
+            FontId {
+                size: self.content_width() * 0.07,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        let padding = self.content_width() * 0.01;
+        let size = digits.size() + Vec2::splat(padding * 2.0);
+        let frame = Rect::from_min_size(
+            Pos2 {
+                x: center.x - size.x / 2.0,
+                y: top,
+            },
+            size,
+        );
+        canvas.rect_stroke(
+            frame,
+            padding,
+            Stroke {
+                width: self.line_width(),
+                color: text_color,
+            },
+        );
+        canvas.galley(frame.min + Vec2::splat(padding), digits, text_color);
+    }
+
+    fn paint_sparkline(&self, canvas: &mut Canvas, rect: Rect, history: &VecDeque<f64>) {
+        if history.len() < 2 {
+            return;
+        }
+
+        let center = self.content_center(rect);
+        let width = self.content_width() * 0.4;
+        let height = self.content_width() * 0.08;
+        let top = center.y + self.content_width() * 0.11 + self.statistics_height();
+
+        let low = history.iter().copied().fold(f64::INFINITY, f64::min);
+        let high = history.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+        let step = width / (history.len() - 1) as f32;
+
+        let points = history
+            .iter()
+            .enumerate()
+            .map(|(i, &v)| {
+                let fraction = if high > low {
+                    ((v - low) / (high - low)) as f32
+                } else {
+                    0.5
+                };
+                Pos2 {
+                    x: center.x - width / 2.0 + i as f32 * step,
+                    y: top + height - fraction * height,
+                }
+            })
+            .collect();
+        canvas.add(Shape::line(
+            points,
+            Stroke {
+                width: self.line_width(),
+                color: self.accent_color(),
+            },
+        ));
+    }
+
+    fn write_values_around_circle(&self, canvas: &mut Canvas, rect: Rect, text_color: Color32) {
+        if self.dense {
+            return;
+        }
+        let (values, size) = if self.is_compact() {
+            (
+                vec![self.min_value, self.max_value],
+                (self.inner_width() / 15.0).max(self.min_font_size()),
+            )
+        } else {
+            (
+                self.labels.values(self.min_value, self.max_value),
+                self.inner_width() / 15.0,
+            )
+        };
+        for (pos, galley) in self.layout_labels(canvas, rect, values, size, text_color) {
+            canvas.galley(pos - galley.size() / 2.0, galley, text_color);
+        }
+    }
+
+    /// Lay out the labels at the given values around the circle, leaving out labels and then
+    /// shrinking the font until none of them overlap
+    fn layout_labels(
+        &self,
+        canvas: &Canvas,
+        rect: Rect,
+        values: Vec<f64>,
+        mut size: f32,
+        text_color: Color32,
+    ) -> Vec<(Pos2, Arc<Galley>)> {
+        let overlapping = |labels: &[(Pos2, Arc<Galley>)]| {
+            labels.windows(2).any(|pair| {
+                let [(a, a_galley), (b, b_galley)] = pair else {
+                    return false;
+                };
+                Rect::from_center_size(*a, a_galley.size())
+                    .intersects(Rect::from_center_size(*b, b_galley.size()))
+            })
+        };
+
+        let mut values = values;
+        loop {
+            let labels: Vec<_> = values
+                .iter()
+                .map(|&value| {
+                    let angle = self.value_to_angle(value);
+                    // the arc is drawn inside of the radius, so thick arcs don't need labels to
+                    // be any further out than the default one does, where they fit in the rect
+                    let radius = self.radius() + self.thickness().min(self.inner_width() / 15.0);
+                    let pos = Pos2 {
+                        x: self.x_f(rect, angle, radius),
+                        y: self.y_f(rect, angle, radius),
+                    };
+                    let galley = canvas.layout_no_wrap(
+                        self.labels.format(value, self.min_value, self.max_value),
+                        FontId {
+                            size,
+                            family: FontFamily::Monospace,
+                        },
+                        text_color,
+                    );
+                    (pos, galley)
+                })
+                .collect();
+            if !overlapping(&labels) || size < self.min_font_size() / 2.0 {
+                return labels;
+            }
+            if values.len() > 2 {
+                values = scale::thin_out(values);
+            } else {
+                size *= 0.8;
+            }
+        }
+    }
+
+    fn center_value_font(&self) -> FontId {
+        FontId {
+            size: if self.is_compact() {
+                self.content_width() / 3.5
+            } else {
+                self.content_width() / 5.0
+            },
+            family: FontFamily::Monospace,
+        }
+    }
+
+    /// Write the value in the center, shrinking the font for long values so that they stay inside
+    /// the arc, and cutting them short if even the smallest font is too wide
+    fn write_center_value(&self, canvas: &mut Canvas, rect: Rect, text_color: Color32) {
+        let text = self.value_text();
+        let max_width = self.content_width() * 0.7;
+        let mut font = self.center_value_font();
+        let mut galley = canvas.layout_no_wrap(text.clone(), font.clone(), text_color);
+        if galley.size().x > max_width {
+            let smallest = font.size.min(self.min_font_size());
+            font.size = (font.size * max_width / galley.size().x).max(smallest);
+            galley = canvas.layout_elided(text, font, text_color, max_width);
+        }
+        let pos = self.content_center(rect) - galley.size() / 2.0;
+        canvas.galley(pos, galley, text_color);
+    }
+
+    fn paint_trail(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        trail: &VecDeque<f64>,
+        arc_color: Color32,
+    ) {
+        let radius = self.radius() - self.thickness() / 2.0;
+        for (i, &value) in trail.iter().enumerate() {
+            let angle = self.value_to_angle(value);
+            let age = (i + 1) as f32 / (trail.len() + 1) as f32;
+            canvas.circle_stroke(
+                Pos2 {
+                    x: self.x_f(rect, angle, radius),
+                    y: self.y_f(rect, angle, radius),
+                },
+                self.thickness() / 2.0,
+                Stroke {
+                    width: self.line_width(),
+                    color: arc_color.gamma_multiply(age),
+                },
+            );
+        }
+    }
+
+    fn paint_value_circle(&self, canvas: &mut Canvas, rect: Rect, angle: i32, arc_color: Color32) {
+        let knob_color = self
+            .style
+            .knob_color
+            .unwrap_or(canvas.style().visuals.extreme_bg_color);
+        canvas.circle(
+            Pos2 {
+                x: self.x_f(rect, angle, self.radius() - self.thickness() / 2.0),
+                y: self.y_f(rect, angle, self.radius() - self.thickness() / 2.0),
+            },
+            self.thickness() / 2.0,
+            knob_color,
+            Stroke {
+                width: self.line_width(),
+                color: arc_color,
+            },
+        );
+    }
+
+    /// An outline around the arc for a latched alarm
+    fn paint_alarm_outline(&self, canvas: &mut Canvas, rect: Rect) {
+        let stroke = Stroke {
+            width: self.line_width() * 2.0,
+            color: canvas.style().visuals.error_fg_color,
+        };
+        let radius = self.radius() + self.thickness() / 4.0 + stroke.width / 2.0;
+        canvas.add(Shape::line(
+            self.arc(rect, self.dial.end_angle(), self.dial.start_angle(), radius),
+            stroke,
+        ));
+    }
+
+    fn paint_focus_outline(&self, canvas: &mut Canvas, rect: Rect) {
+        let stroke = canvas.style().visuals.selection.stroke;
+        let radius = self.radius() + self.thickness() / 4.0 + stroke.width / 2.0;
+        canvas.add(Shape::line(
+            self.arc(rect, self.dial.end_angle(), self.dial.start_angle(), radius),
+            stroke,
+        ));
+    }
+
+    /// A small line across the arc at the given value
+    fn paint_marker(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        value: f64,
+        width: f32,
+        color: Color32,
+    ) {
+        let angle = self.value_to_angle(self.clamp_to_range(value));
+        let inner = self.radius() - self.thickness() * 1.2;
+        let outer = self.radius() + self.thickness() * 0.2;
+        canvas.line_segment(
+            [
+                Pos2 {
+                    x: self.x_f(rect, angle, inner),
+                    y: self.y_f(rect, angle, inner),
+                },
+                Pos2 {
+                    x: self.x_f(rect, angle, outer),
+                    y: self.y_f(rect, angle, outer),
+                },
+            ],
+            Stroke { width, color },
+        );
+    }
+
+    fn paint_target(&self, canvas: &mut Canvas, rect: Rect, target: f64, color: Color32) {
+        let angle = self.value_to_angle(self.clamp_to_range(target));
+        let tip = self.radius() - self.thickness() * 1.1;
+        let base = self.radius() - self.thickness() * 1.7;
+        canvas.add(Shape::convex_polygon(
+            vec![
+                Pos2 {
+                    x: self.x_f(rect, angle, tip),
+                    y: self.y_f(rect, angle, tip),
+                },
+                Pos2 {
+                    x: self.x_f(rect, angle + 4, base),
+                    y: self.y_f(rect, angle + 4, base),
+                },
+                Pos2 {
+                    x: self.x_f(rect, angle - 4, base),
+                    y: self.y_f(rect, angle - 4, base),
+                },
+            ],
+            color,
+            Stroke::NONE,
+        ));
+    }
+
+    fn paint_rate_needle(&self, canvas: &mut Canvas, rect: Rect, rate: f64, full_scale: f64) {
+        let angle = self.rate_to_angle(rate, full_scale);
+        let inner = self.radius() - self.thickness() * 2.0;
+        let outer = self.radius() - self.thickness() * 1.25;
+        canvas.line_segment(
+            [
+                Pos2 {
+                    x: self.x_f(rect, angle, inner),
+                    y: self.y_f(rect, angle, inner),
                 },
                 Pos2 {
-                    x: self.x_f(rect, -45, self.radius() - self.thickness()),
-                    y: self.y_f(rect, -45, self.radius() - self.thickness()),
+                    x: self.x_f(rect, angle, outer),
+                    y: self.y_f(rect, angle, outer),
                 },
             ],
-            closed: true,
-            fill: bg_color,
-            stroke: Stroke {
-                width: 2.0,
-                color: bg_color,
+            Stroke {
+                width: self.thickness() / 4.0,
+                color: self.accent_color(),
+            },
+        );
+    }
+
+    fn paint_end_caps(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        arc_color: Color32,
+        arc_bg_color: Color32,
+    ) {
+        canvas.circle(
+            Pos2 {
+                x: self.x_f(
+                    rect,
+                    self.dial.start_angle(),
+                    self.radius() - self.thickness() / 2.0,
+                ),
+                y: self.y_f(
+                    rect,
+                    self.dial.start_angle(),
+                    self.radius() - self.thickness() / 2.0,
+                ),
+            },
+            self.thickness() / 2.0,
+            arc_color,
+            Stroke::NONE,
+        );
+        canvas.circle(
+            Pos2 {
+                x: self.x_f(
+                    rect,
+                    self.dial.end_angle(),
+                    self.radius() - self.thickness() / 2.0,
+                ),
+                y: self.y_f(
+                    rect,
+                    self.dial.end_angle(),
+                    self.radius() - self.thickness() / 2.0,
+                ),
+            },
+            self.thickness() / 2.0,
+            arc_bg_color,
+            Stroke::NONE,
+        );
+    }
+
+    /// A "+" on the end cap past the maximum, or a "−" on the one before the minimum, for values
+    /// outside of the range
+    fn paint_overflow_indicator(
+        &self,
+        canvas: &mut Canvas,
+        rect: Rect,
+        above: bool,
+        arc_color: Color32,
+        sign_color: Color32,
+    ) {
+        let angle = if above {
+            self.dial.end_angle()
+        } else {
+            self.dial.start_angle()
+        };
+        let radius = self.radius() - self.thickness() / 2.0;
+        let center = Pos2 {
+            x: self.x_f(rect, angle, radius),
+            y: self.y_f(rect, angle, radius),
+        };
+        // the cap past the maximum is normally the color of the background arc
+        canvas.circle(center, self.thickness() / 2.0, arc_color, Stroke::NONE);
+
+        let half = self.thickness() * 0.3;
+        let stroke = Stroke {
+            width: self
+                .round_to_pixels(self.thickness() / 6.0)
+                .max(self.line_width()),
+            color: sign_color,
+        };
+        canvas.line_segment(
+            [
+                center - egui::vec2(half, 0.0),
+                center + egui::vec2(half, 0.0),
+            ],
+            stroke,
+        );
+        if above {
+            canvas.line_segment(
+                [
+                    center - egui::vec2(0.0, half),
+                    center + egui::vec2(0.0, half),
+                ],
+                stroke,
+            );
+        }
+    }
+
+    /// The band of the arc from the given angle to the start of the dial, drawn as a thick line
+    /// along the middle of the band so that nothing needs to be masked out
+    fn paint_arc(&self, canvas: &mut Canvas, rect: Rect, angle: i32, color: Color32) {
+        if angle >= self.dial.start_angle() {
+            return;
+        }
+        let radius = self.radius() - self.thickness() / 2.0;
+        canvas.add(Shape::line(
+            self.arc(rect, angle, self.dial.start_angle(), radius),
+            Stroke {
+                width: self.thickness(),
+                color,
+            },
+        ));
+    }
+
+    /// Whether the shapes of the gauge only depend on its configuration and value, rather than
+    /// also on time or recent values
+    fn is_cacheable(&self, ui: &Ui, state: &GaugeState) -> bool {
+        let time = ui.input(|i| i.time);
+        let alarm = self.current_zone().is_some_and(Zone::is_alarm);
+        self.rate_of_change.is_none()
+            && self.sparkline.is_none()
+            && self.trail.is_none()
+            && !(alarm && (self.blink_rate.is_some() || self.pulse_rate.is_some()))
+            && !state
+                .flash_time
+                .is_some_and(|flash_time| time - flash_time < FLASH_DURATION)
+    }
+
+    /// Paint the gauge, reusing the shapes from the last frame when nothing they depend on has
+    /// changed, to save generating the arcs and laying out the labels again. Gauges which paint
+    /// again every frame still reuse the list the shapes are collected in.
+    fn paint_cached(
+        &self,
+        ui: &Ui,
+        id: Id,
+        rect: Rect,
+        state: &mut GaugeState,
+        persisted: &PersistedState,
+        has_focus: bool,
+    ) {
+        if !self.is_cacheable(ui, state) {
+            let shapes = std::mem::take(&mut state.shapes);
+            let mut canvas = Canvas::reusing(ui.ctx(), ui.style().clone(), shapes);
+            self.paint(&mut canvas, Some(id), rect, state, persisted, has_focus);
+            let mut shapes = canvas.into_shapes();
+            ui.painter().extend(shapes.drain(..));
+            state.shapes = shapes;
+            return;
+        }
+
+        let paint = || {
+            let mut canvas = Canvas::new(ui.ctx(), ui.style().clone());
+            self.paint(&mut canvas, Some(id), rect, state, persisted, has_focus);
+            canvas.into_shapes()
+        };
+
+        let key = ShapeCache::key(&PaintInputs {
+            rect: Keyed(rect),
+            has_focus,
+            static_inputs: self.static_inputs(),
+            values: Keyed([self.value, self.shown_value]),
+            integral: self.integral,
+            color: self.color,
+            text: &self.text,
+            units: &self.units,
+            previous_zone: self.previous_zone,
+            zone_text_color: self.zone_text_color,
+            editing_zones: self.zone_edit.is_some(),
+            overflow: self.overflow,
+            latch_alarm: self.latch_alarm,
+            debug: self.debug,
+            show_watermarks: self.watermarks,
+            show_statistics: self.statistics,
+            stale_indicator: self.stale_indicator,
+            mirrored: self.is_mirrored(),
+            rates: Keyed([self.blink_rate, self.pulse_rate, self.brightness]),
+            indicators: Keyed([
+                self.linked_hover,
+                self.peak_decay,
+                self.target,
+                self.redline,
+                self.odometer,
+            ]),
+            deviation_band: self.deviation_band,
+            markers: Keyed(self.markers.as_slice()),
+            second_value: Keyed(self.second_value),
+            segments: Keyed(self.segments.as_slice()),
+            last_valid_value: Keyed(state.last_valid_value),
+            peak: Keyed(persisted.peak.map(|(peak, _)| peak)),
+            watermarks: Keyed(persisted.watermarks),
+            alarm_latched: persisted.alarm_latched,
+            statistics: Keyed(persisted.statistics.as_ref()),
+            visuals: VisualsInputs::new(ui.visuals()),
+        });
+        let shapes = ShapeCache::get_or_paint(ui.ctx(), id, key, paint);
+        ui.painter().extend(shapes.iter().cloned());
+    }
+
+    /// Whether the gauge uses any of the state kept between frames
+    fn is_stateful(&self) -> bool {
+        self.rate_of_change.is_some()
+            || self.sparkline.is_some()
+            || self.animation_time.is_some()
+            || self.spring.is_some()
+            || self.flash_threshold.is_some()
+            || self.peak_decay.is_some()
+            || self.watermarks
+            || self.statistics
+            || self.trail.is_some()
+            || self.smoothing.is_some()
+            || self.sampled_at.is_some()
+            || self.stale_indicator
+            || self.on_zone_change.is_some()
+            || self.zones.iter().any(|zone| zone.hysteresis > 0.0)
+            || self.latch_alarm
+            || self.announce_alarms
+    }
+
+    /// Update the state kept between frames for this frame. The state is taken out of egui's
+    /// memory rather than copied, and has to be put back with [`Self::store_state`].
+    fn update_state(&mut self, ui: &Ui, id: Id) -> (GaugeState, PersistedState) {
+        self.shown_value = self.value;
+        // leave the state alone rather than filling it with values that can't be placed on the arc
+        if !self.is_stateful() || !self.has_valid_range() {
+            return Default::default();
+        }
+
+        let time = ui.input(|i| i.time);
+        let mut animating = false;
+        let (mut state, persisted) = ui.data_mut(|d| {
+            let mut state = d.remove_temp::<GaugeState>(id).unwrap_or_default();
+            if !self.value.is_finite() {
+                // leave the filters and animations to carry on once values come back
+                return (state, PersistedState::get(d, id).clone());
+            }
+            if self.stale_indicator {
+                state.last_valid_value = Some(self.value);
+            }
+            // the value given to the gauge, before interpolation and smoothing change it
+            let target = self.value;
+            if let Some((sampled_at, interpolation)) = self.sampled_at {
+                let (value, moving) = state.interpolation.update(
+                    self.value,
+                    sampled_at,
+                    Instant::now(),
+                    interpolation,
+                );
+                self.value = value;
+                self.shown_value = value;
+                animating |= moving;
+            }
+            if let Some(smoothing) = self.smoothing {
+                let raw = self.value;
+                self.value = state.smoothing.update(raw, smoothing);
+                self.shown_value = self.value;
+                // keep filtering until the shown value catches up
+                animating |=
+                    (raw - self.value).abs() > (self.max_value - self.min_value).abs() * 1e-4;
+            }
+            // the zones may have changed since the last frame
+            self.previous_zone = state.zone_index.filter(|&i| i < self.zones.len());
+            state.zone_index = zone::zone_index_at(&self.zones, self.value, state.zone_index);
+            if let Some(spring) = self.spring {
+                // simulate in fractions of the range, so the spring behaves the same for any range
+                let span = self.max_value - self.min_value;
+                let target = (self.value - self.min_value) / span;
+                let needle = state
+                    .spring
+                    .get_or_insert_with(|| SpringState::new(target, time));
+                let (position, moving) = needle.update(target, time, spring);
+                // like a real needle it can't swing past the ends of the scale
+                self.shown_value = self.clamp_to_range(self.min_value + position * span);
+                animating |= moving;
+            } else if let Some(duration) = self.animation_time {
+                let animation = state
+                    .animation
+                    .get_or_insert_with(|| Animation::new(self.value, time));
+                let (shown_value, in_progress) =
+                    animation.update(self.value, time, duration, self.easing);
+                self.shown_value = shown_value;
+                animating |= in_progress;
+            }
+            if self.rate_of_change.is_some() {
+                state.record_sample(time, self.value);
+            }
+            if let Some(len) = self.sparkline {
+                state.record_history(self.value, len);
+            }
+            if let Some(threshold) = self.flash_threshold {
+                if state
+                    .last_value
+                    .is_some_and(|last| (self.value - last).abs() > threshold)
+                {
+                    state.flash_time = Some(time);
+                }
+                state.last_value = Some(self.value);
+            }
+
+            if let Some(len) = self.trail {
+                state.record_trail(self.shown_value, len);
+            }
+
+            if let Some(precision) = self.repaint_precision {
+                animating &= Self::needs_repaint(self.shown_value, target, precision);
+            }
+
+            let persisted = PersistedState::get(d, id);
+            if let Some(decay) = self.peak_decay {
+                // keep repainting until the peak has fallen back to the value
+                animating |=
+                    persisted.update_peak(self.value, time, decay) > self.value && decay > 0.0;
+            }
+            if self.watermarks {
+                persisted.update_watermarks(self.value);
+            }
+            if self.statistics {
+                persisted.update_statistics(self.value);
+            }
+            let entered_zone = state.zone_index.filter(|&i| Some(i) != self.previous_zone);
+            if entered_zone
+                .and_then(|i| self.zones.get(i))
+                .is_some_and(|zone| zone.severity == Severity::Critical)
+            {
+                persisted.alarm_latched |= self.latch_alarm;
+            }
+            if self.announce_alarms && state.zone_index != self.previous_zone {
+                if let Some(announcement) =
+                    self.alarm_announcement(self.previous_zone, state.zone_index)
+                {
+                    state.announcement = Some(announcement);
+                }
+            }
+            (state, persisted.clone())
+        });
+
+        let flashing = state
+            .flash_time
+            .is_some_and(|flash_time| time - flash_time < FLASH_DURATION);
+        if animating || flashing {
+            if self.low_overhead {
+                ui.ctx().request_repaint_after(LOW_OVERHEAD_FRAME_TIME);
+            } else {
+                ui.ctx().request_repaint();
+            }
+        }
+        // keep repainting until the rate settles back to zero
+        if state.rate() != 0.0 {
+            ui.ctx()
+                .request_repaint_after(Duration::from_secs_f64(RATE_WINDOW / 10.0));
+        }
+        // outside of the closure above, so that the callback is free to use the context
+        if self.value.is_finite() && self.on_zone_change.is_some() {
+            let zone = self.current_zone().cloned();
+            if let Some(ZoneCallback(on_zone_change)) = &mut self.on_zone_change {
+                if let Some(previous) = state.zone.replace(zone.clone()) {
+                    if previous != zone {
+                        on_zone_change(previous.as_ref(), zone.as_ref());
+                    }
+                }
+            }
+        }
+        (state, persisted)
+    }
+
+    fn store_state(&self, ui: &Ui, id: Id, state: GaugeState) {
+        if self.is_stateful() && self.has_valid_range() {
+            ui.data_mut(|d| d.insert_temp(id, state));
+        }
+    }
+
+    fn widget_info(&self, enabled: bool) -> egui::WidgetInfo {
+        // only interactive gauges are really sliders, the rest merely display a value
+        let typ = if self.binding.is_some() {
+            egui::WidgetType::Slider
+        } else {
+            egui::WidgetType::ProgressIndicator
+        };
+        egui::WidgetInfo {
+            enabled,
+            label: Some(self.accessible_description()),
+            value: Some(self.value),
+            ..egui::WidgetInfo::new(typ)
+        }
+    }
+
+    /// A description of the reading for screen readers, like "CPU temperature, 72 of 0 to 100 °C,
+    /// warning zone"
+    fn accessible_description(&self) -> String {
+        let mut description = String::new();
+        if !self.text.is_empty() {
+            description += &self.text;
+            description += ", ";
+        }
+        if !self.has_valid_range() {
+            description += "invalid range";
+            return description;
+        }
+        if !self.value.is_finite() {
+            description += "no data";
+            return description;
+        }
+        description += &format!(
+            "{} of {} to {}",
+            self.value_text(),
+            self.min_value,
+            self.max_value
+        );
+        if !self.units.is_empty() {
+            description += " ";
+            description += &self.units;
+        }
+        if let Some(zone) = self.current_zone() {
+            let zone = zone.description();
+            if !zone.is_empty() {
+                description += ", ";
+                description += &zone;
+            }
+        }
+        description
+    }
+
+    fn show_context_menu(&mut self, response: &mut Response) {
+        let reset_value = self.default_value.filter(|_| self.binding.is_some());
+        let mut reset = false;
+        response.context_menu(|ui| {
+            if ui.button("Copy value").clicked() {
+                ui.ctx().copy_text(self.value_text());
+                ui.close_menu();
+            }
+            if reset_value.is_some() && ui.button("Reset").clicked() {
+                reset = true;
+                ui.close_menu();
+            }
+        });
+        if let Some(value) = reset_value.filter(|_| reset) {
+            self.edit_value(value, response);
+        }
+    }
+
+    /// The zone whose band or part of the arc is at the given position, if any
+    fn zone_at_pos(&self, rect: Rect, pos: Pos2) -> Option<&Zone> {
+        if self.zones.is_empty() || !self.has_valid_range() {
+            return None;
+        }
+        // from the inside of the arc to the outside of the zone bands
+        let band_width = self.zone_band_width();
+        let distance = self.offset_from_center(rect, pos).length();
+        let sweep = self.pos_to_sweep(rect, pos);
+        let full_sweep = self.dial.sweep() as f32;
+        if distance < self.radius() - self.thickness()
+            || distance > self.radius() + band_width * 2.5
+            || sweep > full_sweep
+        {
+            return None;
+        }
+        let value =
+            self.min_value + (sweep / full_sweep) as f64 * (self.max_value - self.min_value);
+        zone::zone_at(&self.zones, value)
+    }
+
+    /// The tooltip for a zone, like "Warning: 70–90 °C"
+    fn zone_tooltip_text(&self, zone: &Zone) -> String {
+        let format = |value| self.labels.format(value, self.min_value, self.max_value);
+        let mut text = format!(
+            "{}: {}–{}",
+            zone.title(),
+            format(*zone.range.start()),
+            format(*zone.range.end())
+        );
+        if !self.units.is_empty() {
+            text += " ";
+            text += &self.units;
+        }
+        text
+    }
+
+    fn tooltip_text(&self) -> String {
+        let units = if self.units.is_empty() {
+            String::new()
+        } else {
+            format!(" {}", self.units)
+        };
+        let mut text = format!(
+            "{}{units}\nRange: {} to {}{units}",
+            self.value_text(),
+            self.min_value,
+            self.max_value
+        );
+        if let Some(zone) = self.current_zone() {
+            let zone = zone.description();
+            if !zone.is_empty() {
+                text += "\nZone: ";
+                text += &zone;
+            }
+        }
+        text
+    }
+
+    /// The shapes making up the gauge when it is laid out in the given rect, which should be of
+    /// the [`Self::desired_size`], for compositing it into custom painting or caching it. Text is
+    /// laid out with the fonts of the given context. Since there is no `Ui`, the gauge is shown
+    /// without the state it keeps between frames, like animations and the sparkline, and only
+    /// with its own [`Self::style`] rather than the [`GaugeTheme`].
+    pub fn shapes(&self, ctx: &Context, rect: Rect) -> Vec<Shape> {
+        let mut canvas = Canvas::new(ctx, ctx.style());
+        self.paint(
+            &mut canvas,
+            None,
+            rect,
+            &GaugeState::default(),
+            &PersistedState::default(),
+            false,
+        );
+        canvas.into_shapes()
+    }
+
+    /// Paint the gauge in the given rect, without allocating space for it, for drawing it in a
+    /// plot, on top of a map or on a custom canvas. Like with [`Self::shapes`], the rect should
+    /// be of the [`Self::desired_size`], and the gauge doesn't respond to input. With the
+    /// `egui_plot` feature, gauges can be added to plots as a `PlotGauge` instead.
+    pub fn paint_at(&self, painter: &Painter, rect: Rect) {
+        painter.extend(self.shapes(painter.ctx(), rect));
+    }
+
+    /// The angle of the given value on the arc, in degrees counter-clockwise from the positive x
+    /// axis, with y pointing up. Values outside of the range stop at the ends of the arc.
+    ///
+    /// This and the other geometry functions work with the rect of the gauge's [`Response`], for
+    /// drawing annotations lined up with the scale and for building interactions on top of the
+    /// response. Show the gauge with `ui.add(&mut gauge)` to keep it around for them; they then
+    /// describe the gauge as it was last shown, fitted to the available space, in the theme's
+    /// proportions and facing the direction of the `Ui` it was shown in.
+    pub fn screen_angle(&self, value: impl emath::Numeric) -> f32 {
+        let angle = self.value_to_angle(value.to_f64()) as f32;
+        if self.is_mirrored() {
+            180.0 - angle
+        } else {
+            angle
+        }
+    }
+
+    /// The center of the dial for a gauge shown in the given rect. For quarter dials this is a
+    /// corner of the arc's rect rather than the middle of the gauge.
+    pub fn dial_center(&self, rect: Rect) -> Pos2 {
+        let center = self.center(self.laid_out_geometry().arc_rect(rect));
+        if self.is_mirrored() {
+            Pos2 {
+                x: 2.0 * rect.center().x - center.x,
+                ..center
+            }
+        } else {
+            center
+        }
+    }
+
+    /// The distance from the center of the dial to the outside of the arc. The arc is drawn
+    /// inwards from there, and the scale labels are just outside of it.
+    pub fn dial_radius(&self) -> f32 {
+        self.laid_out_geometry().radius
+    }
+
+    /// The width of the arc
+    pub fn arc_thickness(&self) -> f32 {
+        self.laid_out_geometry().thickness
+    }
+
+    /// The point at the given distance from the center of the dial in the direction of the given
+    /// value, for a gauge shown in the given rect. Vertical distances are stretched by the
+    /// [`Self::aspect_ratio`] like the dial is. With a radius of
+    /// `dial_radius() - arc_thickness() / 2.0` this is the middle of the arc.
+    pub fn screen_pos(&self, rect: Rect, value: impl emath::Numeric, radius: f32) -> Pos2 {
+        let angle = self.screen_angle(value).to_radians();
+        let center = self.dial_center(rect);
+        Pos2 {
+            x: center.x + angle.cos() * radius,
+            y: center.y - angle.sin() * radius * self.aspect_ratio,
+        }
+    }
+
+    /// The value at the point of the arc nearest to the given position, like when the gauge is
+    /// dragged, for a gauge shown in the given rect. Positions in the gap at the bottom of the
+    /// dial snap to whichever end is closer.
+    pub fn value_at(&self, rect: Rect, pos: Pos2) -> f64 {
+        self.pos_to_value(self.laid_out_geometry().arc_rect(rect), pos)
+    }
+
+    /// Render the gauge to an image of its [`Self::desired_size`] times the given pixels per
+    /// point, in the colors of the given visuals, for embedding the current reading into reports
+    /// or showing it on displays without a GPU. The image is rendered in software with its own
+    /// `Context`, so no window or frame of the app is needed. Its background is transparent, so
+    /// that it can be composited over any surface.
+    ///
+    /// The same gauge and visuals always give the same pixels with the same version of egui:
+    /// the clock of the `Context` stays at zero, so animations, blinking and smoothing are frozen
+    /// at their start, and no state is kept from earlier frames. This makes the images suitable
+    /// for snapshot tests of an app's own gauge configurations.
+    #[cfg(feature = "render")]
+    pub fn render_to_image(&self, visuals: &Visuals, pixels_per_point: f32) -> egui::ColorImage {
+        let ctx = render::offscreen_context(visuals, pixels_per_point);
+        let size = self.desired_size();
+        let mut image = None;
+        let _ = ctx.run(Default::default(), |ctx| {
+            let shapes = self.shapes(ctx, Rect::from_min_size(Pos2::ZERO, size));
+            image = Some(render::render(
+                ctx,
+                shapes,
+                size,
+                pixels_per_point,
+                Color32::TRANSPARENT,
+            ));
+        });
+        image.expect("the frame always runs")
+    }
+
+    /// Write the gauge as an SVG document of its [`Self::desired_size`], in the colors of the given
+    /// visuals, for embedding the current reading into generated HTML or PDF reports at full
+    /// vector quality. Arcs and lines become paths and text becomes text elements in a monospace
+    /// font, stretched to the width egui lays it out with. Like [`Self::render_to_image`], it is
+    /// drawn with its own `Context` and without the state kept between frames.
+    #[cfg(feature = "svg")]
+    pub fn to_svg(&self, visuals: &Visuals) -> String {
+        let ctx = Context::default();
+        ctx.set_visuals(visuals.clone());
+        let size = self.desired_size();
+        let mut shapes = Vec::new();
+        let _ = ctx.run(Default::default(), |ctx| {
+            shapes = self.shapes(ctx, Rect::from_min_size(Pos2::ZERO, size));
+        });
+        svg::to_svg(&shapes, size)
+    }
+
+    /// Show the gauge like `ui.add` does, also returning what it showed
+    pub fn show(mut self, ui: &mut Ui) -> egui::InnerResponse<GaugeOutput> {
+        let response = self.add_contents(ui);
+        egui::InnerResponse::new(self.output(response.rect), response)
+    }
+
+    /// What the gauge shows when it is shown in the given rect
+    fn output(&self, rect: Rect) -> GaugeOutput {
+        let radius = self.radius() - self.thickness() / 2.0;
+        let arc_rect = self.arc_rect(rect);
+        let x = self.x_f(arc_rect, self.angle(), radius);
+        GaugeOutput {
+            value: self.value,
+            range: self.min_value..=self.max_value,
+            units: self.units.clone(),
+            rect,
+            indicator: Pos2 {
+                x: if self.is_mirrored() {
+                    2.0 * rect.center().x - x
+                } else {
+                    x
+                },
+                y: self.y_f(arc_rect, self.angle(), radius),
             },
-        }));
+            zone: self.current_zone().cloned(),
+            clamped: self.overflow_direction().is_some(),
+        }
     }
 
     fn add_contents(&mut self, ui: &mut Ui) -> Response {
-        let desired_size = egui::vec2(self.size, self.size);
+        self.pixels_per_point = ui.ctx().pixels_per_point();
+        self.layout_right_to_left = ui.layout().prefer_right_to_left();
+        self.style = std::mem::take(&mut self.style).or(&GaugeTheme::get(ui.ctx()).gauge);
+        if let Some(fit) = self.fit {
+            self.size = self.fitted_size(ui.available_size(), fit);
+        }
+        self.laid_out = Some(self.geometry());
+        let desired_size = self.desired_size();
+        let mut sense = self.sense;
+        if self.binding.is_some() {
+            sense = sense.union(Sense::click_and_drag());
+        }
+        if self.zone_edit.is_some() {
+            sense = sense.union(Sense::drag());
+        }
+        if self.context_menu || self.latch_alarm {
+            sense = sense.union(Sense::click());
+        }
         let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+        let id = self.id.unwrap_or_else(|| {
+            self.id_source
+                .map_or(response.id, |id_source| ui.make_persistent_id(id_source))
+        });
+        let mut response = ui.interact(rect, id, sense);
+        #[cfg(feature = "watch")]
+        if let Some(receiver) = &self.watch {
+            watch::repaint_on_change(ui.ctx(), receiver);
+        }
+        if !self.handle_zone_editing(ui, self.arc_rect(rect), &mut response) {
+            self.handle_interaction(ui, self.arc_rect(rect), &mut response);
+        }
+        self.update_link_group(ui, self.arc_rect(rect), &response);
+        if self.context_menu {
+            self.show_context_menu(&mut response);
+        }
 
-        response.widget_info(|| egui::WidgetInfo::slider(self.value, &self.text));
+        if self.latch_alarm && response.clicked() {
+            Self::acknowledge(ui.ctx(), response.id);
+        }
+
+        let enabled = ui.is_enabled();
+        response.widget_info(|| self.widget_info(enabled));
 
+        let (mut state, persisted) = self.update_state(ui, response.id);
+        #[cfg(feature = "accesskit")]
+        self.announce(ui, response.id, &state);
         if ui.is_rect_visible(rect) {
-            self.paint(ui, rect);
+            self.paint_cached(
+                ui,
+                response.id,
+                rect,
+                &mut state,
+                &persisted,
+                response.has_focus(),
+            );
         }
+        self.store_state(ui, response.id, state);
 
+        if self.show_tooltip {
+            // only format the text while it is actually shown
+            response = response.on_hover_ui(|ui| {
+                ui.label(self.tooltip_text());
+            });
+        }
+        let hovered_zone = response
+            .hover_pos()
+            .and_then(|pos| self.zone_at_pos(self.arc_rect(rect), pos));
+        if let Some(zone) = hovered_zone {
+            let text = self.zone_tooltip_text(zone);
+            response = response.on_hover_text(text);
+        }
+        if self.on_hover.is_some() && response.hovered() {
+            let output = self.output(response.rect);
+            if let Some(HoverCallback(on_hover)) = &mut self.on_hover {
+                response = response.on_hover_ui(|ui| on_hover(ui, &output));
+            }
+        }
         response
     }
 }
 
-impl egui::Widget for Gauge {
+impl egui::Widget for Gauge<'_> {
     fn ui(mut self, ui: &mut Ui) -> Response {
         self.add_contents(ui)
     }
 }
+
+/// A gauge showing 0 out of 0 to 100, 200 points wide, in blue, for configuring everything with the
+/// builder methods or setting it up from a config file
+impl Default for Gauge<'_> {
+    fn default() -> Self {
+        Self::new(0.0, 0.0..=100.0, 200.0, Color32::BLUE)
+    }
+}
+
+/// Shows a gauge which is kept between frames. Showing it changes its size when it fits itself to
+/// the available space, merges in the theme and smooths the value only for that frame, so that the
+/// gauge is set up the same way for the next one. The geometry functions like
+/// [`Gauge::dial_radius`] go on describing the dial as it was shown.
+impl egui::Widget for &mut Gauge<'_> {
+    fn ui(self, ui: &mut Ui) -> Response {
+        if let Some(Binding(binding)) = &mut self.binding {
+            self.value = binding(None);
+        }
+        let value = self.value;
+        let size = self.size;
+        let style = self.style.clone();
+        let response = self.add_contents(ui);
+        if self.binding.is_none() {
+            self.value = value;
+        }
+        self.size = size;
+        self.style = style;
+        response
+    }
+}
@@ -0,0 +1,302 @@
+//! A gauge which displays a value along a straight bar
+
+use crate::scale::{self, Scale};
+use crate::zone::{self, Zone};
+use crate::{is_valid_range, ordered_range, themed_text_color, themed_track_color};
+use egui::{Align, Align2, FontFamily, FontId, Rect, Response, Sense, Ui};
+use epaint::{Color32, Pos2, Stroke};
+use std::ops::RangeInclusive;
+
+/// A horizontal bar which displays a value as part of a range. It works like a
+/// [`Gauge`](crate::Gauge), but with a straight track instead of an arc.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LinearGauge {
+    value: f64,
+    min_value: f64,
+    max_value: f64,
+    width: f32,
+    color: Color32,
+    text: String,
+    labels: scale::Labels,
+    zones: Vec<Zone>,
+    right_to_left: Option<bool>,
+    mirrored: bool,
+}
+
+impl LinearGauge {
+    /// Create a linear gauge which displays the given value as part of the given range. The given
+    /// width is the width of the gauge, and its height is proportional to it. The given color is
+    /// the color used for the filled in part of the bar. Reversed ranges work like their normal
+    /// counterparts, and empty or infinite ranges show a greyed out bar.
+    pub fn new<Num: emath::Numeric>(
+        value: Num,
+        range: RangeInclusive<Num>,
+        width: f32,
+        color: Color32,
+    ) -> Self {
+        let (min_value, max_value) = ordered_range(&range);
+        Self {
+            value: value.to_f64(),
+            min_value,
+            max_value,
+            width,
+            color,
+            text: Default::default(),
+            labels: Default::default(),
+            zones: Vec::new(),
+            right_to_left: None,
+            mirrored: false,
+        }
+    }
+
+    /// Text to be displayed above the left end of the bar
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Add a zone to the gauge. When zones overlap, the one added last wins.
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    /// Fill the bar from the right for right to left languages, with the value and text swapping
+    /// ends too. By default this follows whether the layout prefers right to left.
+    pub fn right_to_left(mut self, right_to_left: bool) -> Self {
+        self.right_to_left = Some(right_to_left);
+        self
+    }
+
+    /// Use the range, labels and zones of the given scale, replacing any zones added before
+    pub fn scale(mut self, scale: &Scale) -> Self {
+        self.min_value = scale.min_value;
+        self.max_value = scale.max_value;
+        self.labels = scale.labels;
+        self.zones = scale.zones.clone();
+        self
+    }
+
+    fn height(&self) -> f32 {
+        self.width / 4.0
+    }
+
+    fn text_clearance(&self) -> f32 {
+        self.width / 20.0
+    }
+
+    fn thickness(&self) -> f32 {
+        self.height() * 0.15
+    }
+
+    fn track_rect(&self, rect: Rect) -> Rect {
+        Rect::from_center_size(
+            Pos2 {
+                x: rect.center().x,
+                y: rect.top() + self.height() / 2.0,
+            },
+            egui::vec2(rect.width() - self.text_clearance() * 2.0, self.thickness()),
+        )
+    }
+
+    fn value_to_x(&self, track: Rect, v: f64) -> f32 {
+        let fraction = ((v - self.min_value) / (self.max_value - self.min_value)).clamp(0.0, 1.0);
+        if self.mirrored {
+            track.right() - track.width() * fraction as f32
+        } else {
+            track.left() + track.width() * fraction as f32
+        }
+    }
+
+    /// The given anchor, or the one on the other side when the gauge is mirrored
+    fn anchor(&self, anchor: Align2) -> Align2 {
+        if !self.mirrored {
+            return anchor;
+        }
+        let x = match anchor.x() {
+            Align::Min => Align::Max,
+            Align::Center => Align::Center,
+            Align::Max => Align::Min,
+        };
+        Align2([x, anchor.y()])
+    }
+
+    /// The x of the given end of the track, which swap over when the gauge is mirrored
+    fn end_x(&self, track: Rect, right: bool) -> f32 {
+        if right != self.mirrored {
+            track.right()
+        } else {
+            track.left()
+        }
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        if !is_valid_range(self.min_value, self.max_value) {
+            self.paint_invalid(ui, rect, text_color.gamma_multiply(0.5));
+            return;
+        }
+        let color = zone::zone_at(&self.zones, self.value).map_or(self.color, |zone| zone.color);
+
+        let track = self.track_rect(rect);
+        self.paint_track(ui, track, color);
+        self.write_value(ui, rect, track, text_color);
+        self.write_values_along_track(ui, track, text_color);
+
+        if !self.text.is_empty() {
+            self.write_text(ui, track, text_color);
+        }
+    }
+
+    /// A greyed out bar with a dash in place of the value, for ranges which values can't be
+    /// placed on
+    fn paint_invalid(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
+        let track = self.track_rect(rect);
+        ui.painter().rect_filled(
+            track,
+            self.thickness() / 2.0,
+            themed_track_color(ui).gamma_multiply(0.5),
+        );
+        self.write_value_text(ui, rect, track, "—".to_owned(), text_color);
+        if !self.text.is_empty() {
+            self.write_text(ui, track, text_color);
+        }
+    }
+
+    fn paint_track(&mut self, ui: &mut Ui, track: Rect, color: Color32) {
+        let rounding = self.thickness() / 2.0;
+        ui.painter()
+            .rect_filled(track, rounding, themed_track_color(ui));
+
+        let mut filled = track;
+        if self.mirrored {
+            filled.min.x = self.value_to_x(track, self.value);
+        } else {
+            filled.max.x = self.value_to_x(track, self.value);
+        }
+        ui.painter().rect_filled(filled, rounding, color);
+    }
+
+    fn write_value(&mut self, ui: &mut Ui, rect: Rect, track: Rect, text_color: Color32) {
+        self.write_value_text(ui, rect, track, self.value.to_string(), text_color);
+    }
+
+    fn write_value_text(
+        &mut self,
+        ui: &mut Ui,
+        rect: Rect,
+        track: Rect,
+        text: String,
+        text_color: Color32,
+    ) {
+        ui.painter().text(
+            Pos2 {
+                x: self.end_x(track, true),
+                y: rect.top() + self.height() * 0.2,
+            },
+            self.anchor(Align2::RIGHT_CENTER),
+            text,
+            FontId {
+                size: self.height() * 0.25,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn write_text(&mut self, ui: &mut Ui, track: Rect, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: self.end_x(track, false),
+                y: track.top() - self.height() * 0.15,
+            },
+            self.anchor(Align2::LEFT_BOTTOM),
+            self.text.clone(),
+            FontId {
+                size: self.height() * 0.16,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn write_values_along_track(&mut self, ui: &mut Ui, track: Rect, text_color: Color32) {
+        if self.labels.has_zero_tick(self.min_value, self.max_value) {
+            let x = self.value_to_x(track, 0.0);
+            ui.painter().line_segment(
+                [
+                    Pos2 {
+                        x,
+                        y: track.top() - self.height() * 0.04,
+                    },
+                    Pos2 {
+                        x,
+                        y: track.bottom() + self.height() * 0.12,
+                    },
+                ],
+                Stroke {
+                    width: 2.0,
+                    color: text_color,
+                },
+            );
+        }
+        for value in self.labels.values(self.min_value, self.max_value) {
+            let x = self.value_to_x(track, value);
+            ui.painter().line_segment(
+                [
+                    Pos2 {
+                        x,
+                        y: track.bottom() + self.height() * 0.04,
+                    },
+                    Pos2 {
+                        x,
+                        y: track.bottom() + self.height() * 0.12,
+                    },
+                ],
+                Stroke {
+                    width: 1.0,
+                    color: text_color,
+                },
+            );
+            ui.painter().text(
+                Pos2 {
+                    x,
+                    y: track.bottom() + self.height() * 0.14,
+                },
+                Align2::CENTER_TOP,
+                self.labels.format(value, self.min_value, self.max_value),
+                FontId {
+                    size: self.height() * 0.16,
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        self.mirrored = self
+            .right_to_left
+            .unwrap_or(ui.layout().prefer_right_to_left());
+        let desired_size = egui::vec2(self.width, self.height());
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            label: (!self.text.is_empty()).then(|| self.text.clone()),
+            value: Some(self.value),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for LinearGauge {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,17 @@
+//! Extra markers on the scale of a gauge, for limits, references and annotations
+
+use epaint::Color32;
+
+/// How a marker added with [`Gauge::marker`](crate::Gauge::marker) is drawn at its value
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum MarkerStyle {
+    /// A triangle pointing at the arc from the inside, like the target
+    Triangle(Color32),
+    /// A line across the arc, like the redline but thinner
+    Line(Color32),
+    /// A dot in the middle of the arc
+    Dot(Color32),
+    /// Small text inside of the arc, next to a short tick
+    Label(String, Color32),
+}
@@ -0,0 +1,19 @@
+//! What a gauge shows when its value is outside of its range
+
+use epaint::Color32;
+
+/// How a [`Gauge`](crate::Gauge) shows a value which is outside of its range. The indicator
+/// always stops at the end of the arc, and the value in the center is always the real one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum OverflowBehavior {
+    /// Stop at the end of the arc without any other sign that the value is past it
+    #[default]
+    Clamp,
+    /// Stop at the end of the arc and mark the end cap with a "+" when the value is above the
+    /// range, or a "−" when it is below
+    Indicate,
+    /// Stop at the end of the arc and use the given color for the arc, in place of the color of
+    /// the gauge or its zones
+    Saturate(Color32),
+}
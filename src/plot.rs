@@ -0,0 +1,121 @@
+//! Gauges shown as items of `egui_plot` plots
+
+use crate::Gauge;
+use egui::{Align2, Id, Rect, Shape, Ui};
+use egui_plot::{Corner, PlotBounds, PlotGeometry, PlotItem, PlotPoint, PlotTransform};
+use epaint::Color32;
+
+/// How far (in points) a gauge in a corner is kept from the edges of the plot
+const CORNER_MARGIN: f32 = 8.0;
+
+enum Placement {
+    Point(PlotPoint),
+    Corner(Corner),
+}
+
+/// A gauge added to a plot with `PlotUi::add`, either centered on a point of the plot, where it
+/// moves along as the plot is dragged and zoomed, or in a corner of the plot, like a dial showing
+/// the current value of a time series. The gauge is painted as with [`Gauge::shapes`], so it
+/// doesn't respond to input.
+pub struct PlotGauge {
+    gauge: Gauge<'static>,
+    placement: Placement,
+    name: String,
+    id: Option<Id>,
+    highlight: bool,
+}
+
+impl PlotGauge {
+    /// Show the gauge centered on the given point of the plot
+    pub fn new(gauge: Gauge<'static>, point: impl Into<PlotPoint>) -> Self {
+        Self::with_placement(gauge, Placement::Point(point.into()))
+    }
+
+    /// Show the gauge in the given corner of the plot
+    pub fn in_corner(gauge: Gauge<'static>, corner: Corner) -> Self {
+        Self::with_placement(gauge, Placement::Corner(corner))
+    }
+
+    fn with_placement(gauge: Gauge<'static>, placement: Placement) -> Self {
+        Self {
+            gauge,
+            placement,
+            name: String::new(),
+            id: None,
+            highlight: false,
+        }
+    }
+
+    /// Name of the gauge in the legend of the plot. Gauges without a name aren't listed.
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Id of the gauge in the plot, for hiding it with `Plot::hidden_items` and the like
+    pub fn id(mut self, id: Id) -> Self {
+        self.id = Some(id);
+        self
+    }
+
+    fn rect(&self, transform: &PlotTransform) -> Rect {
+        let size = self.gauge.desired_size();
+        match &self.placement {
+            Placement::Point(point) => {
+                Rect::from_center_size(transform.position_from_point(point), size)
+            }
+            Placement::Corner(corner) => {
+                let align = match corner {
+                    Corner::LeftTop => Align2::LEFT_TOP,
+                    Corner::RightTop => Align2::RIGHT_TOP,
+                    Corner::LeftBottom => Align2::LEFT_BOTTOM,
+                    Corner::RightBottom => Align2::RIGHT_BOTTOM,
+                };
+                align.align_size_within_rect(size, transform.frame().shrink(CORNER_MARGIN))
+            }
+        }
+    }
+}
+
+impl PlotItem for PlotGauge {
+    fn shapes(&self, ui: &Ui, transform: &PlotTransform, shapes: &mut Vec<Shape>) {
+        shapes.extend(self.gauge.shapes(ui.ctx(), self.rect(transform)));
+    }
+
+    fn initialize(&mut self, _x_range: std::ops::RangeInclusive<f64>) {}
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn color(&self) -> Color32 {
+        self.gauge.accent_color()
+    }
+
+    fn highlight(&mut self) {
+        self.highlight = true;
+    }
+
+    fn highlighted(&self) -> bool {
+        self.highlight
+    }
+
+    fn geometry(&self) -> PlotGeometry<'_> {
+        PlotGeometry::None
+    }
+
+    /// A gauge on a point takes part in fitting the plot to its items, while one in a corner
+    /// stays put whatever the bounds are
+    fn bounds(&self) -> PlotBounds {
+        match &self.placement {
+            Placement::Point(point) => {
+                PlotBounds::from_min_max([point.x, point.y], [point.x, point.y])
+            }
+            Placement::Corner(_) => PlotBounds::NOTHING,
+        }
+    }
+
+    fn id(&self) -> Option<Id> {
+        self.id
+    }
+}
@@ -0,0 +1,128 @@
+//! Rendering gauges to images without a window, for reports, bots and e-ink displays
+
+use egui::epaint::{ClippedPrimitive, ClippedShape, Primitive, Vertex};
+use egui::{ColorImage, Context, FontImage, Pos2, Rect, Shape, Visuals};
+use epaint::Color32;
+
+/// Tessellate the given shapes, which fit in a rect of the given size at the origin, and fill in
+/// the triangles on a software canvas of that size times `pixels_per_point`
+pub(crate) fn render(
+    ctx: &Context,
+    shapes: Vec<Shape>,
+    size: egui::Vec2,
+    pixels_per_point: f32,
+    background: Color32,
+) -> ColorImage {
+    let clip_rect = Rect::from_min_size(Pos2::ZERO, size);
+    let primitives = ctx.tessellate(
+        shapes
+            .into_iter()
+            .map(|shape| ClippedShape { clip_rect, shape })
+            .collect(),
+        pixels_per_point,
+    );
+    let font_image = ctx.fonts(|f| f.image());
+    let texels: Vec<Color32> = font_image.srgba_pixels(None).collect();
+
+    let width = (size.x * pixels_per_point).ceil() as usize;
+    let height = (size.y * pixels_per_point).ceil() as usize;
+    let mut image = ColorImage::new([width, height], background);
+    for ClippedPrimitive { primitive, .. } in primitives {
+        // only the font texture is used, which also has the white texel for untextured shapes
+        let Primitive::Mesh(mesh) = primitive else {
+            continue;
+        };
+        for triangle in mesh.indices.chunks_exact(3) {
+            let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+            fill_triangle(
+                &mut image,
+                [a, b, c],
+                pixels_per_point,
+                &font_image,
+                &texels,
+            );
+        }
+    }
+    image
+}
+
+fn fill_triangle(
+    image: &mut ColorImage,
+    vertices: [&Vertex; 3],
+    pixels_per_point: f32,
+    font_image: &FontImage,
+    texels: &[Color32],
+) {
+    let [a, b, c] = vertices.map(|v| v.pos.to_vec2() * pixels_per_point);
+    let area = (b - a).x * (c - a).y - (b - a).y * (c - a).x;
+    if area.abs() < f32::EPSILON {
+        return;
+    }
+
+    let [width, height] = image.size;
+    let min_x = a.x.min(b.x).min(c.x).floor().max(0.0) as usize;
+    let max_x = (a.x.max(b.x).max(c.x).ceil() as usize).min(width);
+    let min_y = a.y.min(b.y).min(c.y).floor().max(0.0) as usize;
+    let max_y = (a.y.max(b.y).max(c.y).ceil() as usize).min(height);
+
+    for y in min_y..max_y {
+        for x in min_x..max_x {
+            // sample at the middle of the pixel
+            let p = egui::vec2(x as f32 + 0.5, y as f32 + 0.5);
+            let edge = |from: egui::Vec2, to: egui::Vec2| {
+                ((to - from).x * (p - from).y - (to - from).y * (p - from).x) / area
+            };
+            let weights = [edge(b, c), edge(c, a), edge(a, b)];
+            if weights.iter().any(|&w| w < 0.0) {
+                continue;
+            }
+
+            let uv = vertices
+                .iter()
+                .zip(weights)
+                .fold(egui::Vec2::ZERO, |uv, (v, w)| uv + v.uv.to_vec2() * w);
+            let texel = sample(font_image, texels, uv);
+            let mut color = [0.0; 4];
+            for (v, w) in vertices.iter().zip(weights) {
+                for (channel, &value) in color.iter_mut().zip(v.color.to_array().iter()) {
+                    *channel += value as f32 * w;
+                }
+            }
+            let src: [u8; 4] = std::array::from_fn(|i| {
+                (color[i] * texel.to_array()[i] as f32 / 255.0).round() as u8
+            });
+            let dst = &mut image.pixels[y * width + x];
+            *dst = blend(
+                Color32::from_rgba_premultiplied(src[0], src[1], src[2], src[3]),
+                *dst,
+            );
+        }
+    }
+}
+
+fn sample(font_image: &FontImage, texels: &[Color32], uv: egui::Vec2) -> Color32 {
+    let [width, height] = font_image.size;
+    let x = ((uv.x * width as f32) as usize).min(width - 1);
+    let y = ((uv.y * height as f32) as usize).min(height - 1);
+    texels[y * width + x]
+}
+
+/// Paint the premultiplied source color over the destination color
+fn blend(src: Color32, dst: Color32) -> Color32 {
+    let keep = 255 - src.a() as u32;
+    let channel = |s: u8, d: u8| (s as u32 + (d as u32 * keep + 127) / 255).min(255) as u8;
+    Color32::from_rgba_premultiplied(
+        channel(src.r(), dst.r()),
+        channel(src.g(), dst.g()),
+        channel(src.b(), dst.b()),
+        channel(src.a(), dst.a()),
+    )
+}
+
+/// A context to lay out and render a gauge with, away from the app's own context
+pub(crate) fn offscreen_context(visuals: &Visuals, pixels_per_point: f32) -> Context {
+    let ctx = Context::default();
+    ctx.set_visuals(visuals.clone());
+    ctx.set_zoom_factor(pixels_per_point);
+    ctx
+}
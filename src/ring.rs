@@ -0,0 +1,108 @@
+//! A minimal circular progress indicator
+
+use crate::{arc_points, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// How many degrees per second the arc of an indeterminate ring turns
+const SPIN_SPEED: f64 = 360.0;
+
+/// A thin ring which fills in clockwise from the top as progress is made, with the percentage
+/// written in the middle
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProgressRing {
+    progress: f32,
+    size: f32,
+    color: Color32,
+    indeterminate: bool,
+}
+
+impl ProgressRing {
+    /// Create a ring showing the given progress, from 0 to 1. The given size is the width and
+    /// height of the ring. The given color is the color of the filled in part of the ring.
+    pub fn new(progress: f32, size: f32, color: Color32) -> Self {
+        Self {
+            progress: progress.clamp(0.0, 1.0),
+            size,
+            color,
+            indeterminate: false,
+        }
+    }
+
+    /// Spin a segment of the ring around instead of showing the progress, for when it isn't
+    /// known how much is left to do
+    pub fn indeterminate(mut self, indeterminate: bool) -> Self {
+        self.indeterminate = indeterminate;
+        self
+    }
+
+    fn thickness(&self) -> f32 {
+        self.size / 15.0
+    }
+
+    fn radius(&self) -> f32 {
+        (self.size - self.thickness()) / 2.0
+    }
+
+    fn paint_arc(&mut self, ui: &mut Ui, center: Pos2, from: f32, to: f32, color: Color32) {
+        ui.painter().add(Shape::line(
+            arc_points(center, self.radius(), to, from),
+            Stroke {
+                width: self.thickness(),
+                color,
+            },
+        ));
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let center = rect.center();
+        let stroke = Stroke {
+            width: self.thickness(),
+            color: themed_track_color(ui).gamma_multiply(0.3),
+        };
+        ui.painter().circle_stroke(center, self.radius(), stroke);
+
+        if self.indeterminate {
+            let start = 90.0 - (ui.input(|i| i.time) * SPIN_SPEED).rem_euclid(360.0) as f32;
+            self.paint_arc(ui, center, start, start - 90.0, self.color);
+            ui.ctx().request_repaint();
+        } else {
+            let end = 90.0 - self.progress * 360.0;
+            if end < 90.0 {
+                self.paint_arc(ui, center, 90.0, end, self.color);
+            }
+            ui.painter().text(
+                center,
+                Align2::CENTER_CENTER,
+                format!("{:.0}%", self.progress * 100.0),
+                FontId {
+                    size: self.size / 5.0,
+                    family: FontFamily::Monospace,
+                },
+                themed_text_color(ui),
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: (!self.indeterminate).then_some(self.progress as f64),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for ProgressRing {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
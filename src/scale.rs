@@ -0,0 +1,244 @@
+//! The scale of values shared by the different kinds of gauges
+
+use crate::zone::Zone;
+use std::ops::RangeInclusive;
+
+/// The range, labels and zones of a quantity, which can be configured once and shared by all the
+/// gauges showing that quantity, so that they stay consistent. Apply it to a gauge with
+/// [`Gauge::scale`](crate::Gauge::scale), [`LinearGauge::scale`](crate::LinearGauge::scale) or
+/// [`Thermometer::scale`](crate::Thermometer::scale).
+#[derive(Clone, Debug, PartialEq)]
+pub struct Scale {
+    pub(crate) min_value: f64,
+    pub(crate) max_value: f64,
+    pub(crate) labels: Labels,
+    pub(crate) units: String,
+    pub(crate) zones: Vec<Zone>,
+}
+
+impl Scale {
+    /// Create a scale covering the given range, labelled at both ends and five values in between
+    pub fn new<Num: emath::Numeric>(range: RangeInclusive<Num>) -> Self {
+        let (min_value, max_value) = crate::ordered_range(&range);
+        Self {
+            min_value,
+            max_value,
+            labels: Labels::default(),
+            units: Default::default(),
+            zones: Vec::new(),
+        }
+    }
+
+    /// How many evenly sized divisions there are between labels. Defaults to 6.
+    pub fn divisions(mut self, divisions: usize) -> Self {
+        self.labels.divisions = divisions.max(1);
+        self
+    }
+
+    /// Write the labels with the given function. By default they are written as whole numbers,
+    /// or with as many decimals as needed to tell them apart on small ranges.
+    pub fn format(mut self, format: fn(f64) -> String) -> Self {
+        self.labels.format = Some(format);
+        self
+    }
+
+    /// Emphasize zero with a tick of its own, for ranges with negative and positive values like
+    /// temperatures or a balance from left to right
+    pub fn zero_tick(mut self, zero_tick: bool) -> Self {
+        self.labels.zero_tick = zero_tick;
+        self
+    }
+
+    /// The units of the values, shown by gauges which show units
+    pub fn units(mut self, units: impl Into<String>) -> Self {
+        self.units = units.into();
+        self
+    }
+
+    /// Add a zone to the scale. When zones overlap, the one added last wins.
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+}
+
+/// Where the labels along a scale go and how they are written
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Labels {
+    divisions: usize,
+    format: Option<fn(f64) -> String>,
+    pub(crate) zero_tick: bool,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Self {
+            divisions: 6,
+            format: None,
+            zero_tick: false,
+        }
+    }
+}
+
+/// Formats are equal when they are the same function
+impl PartialEq for Labels {
+    fn eq(&self, other: &Self) -> bool {
+        let same_format = match (self.format, other.format) {
+            (Some(a), Some(b)) => a as usize == b as usize,
+            (a, b) => a.is_none() && b.is_none(),
+        };
+        self.divisions == other.divisions && same_format && self.zero_tick == other.zero_tick
+    }
+}
+
+/// Formats hash by their address, as they are compared
+impl std::hash::Hash for Labels {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.divisions.hash(state);
+        self.format.map(|format| format as usize).hash(state);
+        self.zero_tick.hash(state);
+    }
+}
+
+impl Labels {
+    /// The values labelled along a scale: both ends and evenly spaced values in between
+    pub(crate) fn values(&self, min: f64, max: f64) -> Vec<f64> {
+        let step = (max - min) / self.divisions as f64;
+        if !step.is_finite() || step <= 0.0 {
+            return vec![min];
+        }
+        (0..=self.divisions)
+            .map(|i| {
+                if i == self.divisions {
+                    max
+                } else {
+                    min + step * i as f64
+                }
+            })
+            .collect()
+    }
+
+    /// Whether zero gets a tick of its own on a scale from `min` to `max`, which it only does when
+    /// it is between the ends
+    pub(crate) fn has_zero_tick(&self, min: f64, max: f64) -> bool {
+        self.zero_tick && min < 0.0 && max > 0.0
+    }
+
+    /// The text of the label for the given value on a scale from `min` to `max`
+    pub(crate) fn format(&self, value: f64, min: f64, max: f64) -> String {
+        match self.format {
+            Some(format) => format(value),
+            None => format_label(value, (max - min) / self.divisions as f64),
+        }
+    }
+}
+
+/// Drop every other label, but always keep the ones at both ends of the scale
+pub(crate) fn thin_out<T>(labels: Vec<T>) -> Vec<T> {
+    let last = labels.len().saturating_sub(1);
+    let mut thinned: Vec<T> = Vec::with_capacity(labels.len() / 2 + 1);
+    for (i, label) in labels.into_iter().enumerate() {
+        if i == last && i % 2 == 1 && thinned.len() > 1 {
+            // rather than leaving the one before crowding the last label
+            thinned.pop();
+        }
+        if i % 2 == 0 || i == last {
+            thinned.push(label);
+        }
+    }
+    thinned
+}
+
+/// The text of the label for the given value on a scale with the given distance between labels:
+/// a whole number, unless the labels are less than one apart, in which case there are just enough
+/// decimals for neighbouring labels to differ
+fn format_label(value: f64, step: f64) -> String {
+    let decimals = if step.is_finite() && step > 0.0 && step < 1.0 {
+        (-step.log10()).ceil().min(6.0) as usize
+    } else {
+        0
+    };
+    // round first, so that small negative values like -0.2 don't turn into "-0" (adding zero
+    // turns negative zero into positive zero)
+    let scale = 10f64.powi(decimals as i32);
+    let value = (value * scale).round() / scale + 0.0;
+    format!("{value:.decimals$}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(scale: &Scale) -> Vec<String> {
+        scale
+            .labels
+            .values(scale.min_value, scale.max_value)
+            .into_iter()
+            .map(|value| scale.labels.format(value, scale.min_value, scale.max_value))
+            .collect()
+    }
+
+    #[test]
+    fn temperature_range_is_labelled_in_whole_numbers() {
+        assert_eq!(
+            labels(&Scale::new(-40..=120)),
+            ["-40", "-13", "13", "40", "67", "93", "120"]
+        );
+        assert_eq!(
+            labels(&Scale::new(-40..=120).divisions(4)),
+            ["-40", "0", "40", "80", "120"]
+        );
+    }
+
+    #[test]
+    fn small_range_gets_decimals_without_negative_zero() {
+        assert_eq!(
+            labels(&Scale::new(-1.0..=1.0)),
+            ["-1.0", "-0.7", "-0.3", "0.0", "0.3", "0.7", "1.0"]
+        );
+        assert_eq!(format_label(-0.2, 10.0), "0");
+        assert_eq!(format_label(-0.04, 0.5), "0.0");
+    }
+
+    #[test]
+    fn reversed_signed_range_is_labelled_like_the_normal_one() {
+        let reversed = Scale::new(RangeInclusive::new(120, -40));
+        assert_eq!((reversed.min_value, reversed.max_value), (-40.0, 120.0));
+        assert_eq!(labels(&reversed), labels(&Scale::new(-40..=120)));
+        assert_eq!(
+            labels(&Scale::new(RangeInclusive::new(1.0, -1.0))),
+            labels(&Scale::new(-1.0..=1.0))
+        );
+    }
+
+    #[test]
+    fn values_include_both_ends() {
+        let values = Labels::default().values(-40.0, 120.0);
+        assert_eq!(values.len(), 7);
+        assert_eq!(values.first(), Some(&-40.0));
+        assert_eq!(values.last(), Some(&120.0));
+    }
+
+    #[test]
+    fn empty_range_has_a_single_label() {
+        assert_eq!(Labels::default().values(5.0, 5.0), [5.0]);
+        assert_eq!(Labels::default().values(0.0, f64::INFINITY), [0.0]);
+    }
+
+    #[test]
+    fn zero_tick_only_between_the_ends() {
+        let labels = Scale::new(-40..=120).zero_tick(true).labels;
+        assert!(labels.has_zero_tick(-40.0, 120.0));
+        assert!(labels.has_zero_tick(-1.0, 1.0));
+        assert!(!labels.has_zero_tick(0.0, 100.0));
+        assert!(!labels.has_zero_tick(-100.0, 0.0));
+        assert!(!labels.has_zero_tick(10.0, 20.0));
+        assert!(!Labels::default().has_zero_tick(-40.0, 120.0));
+    }
+
+    #[test]
+    fn custom_format_is_used() {
+        let scale = Scale::new(0..=100).divisions(2).format(|v| format!("{v}%"));
+        assert_eq!(labels(&scale), ["0%", "50%", "100%"]);
+    }
+}
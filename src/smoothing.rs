@@ -0,0 +1,42 @@
+//! Filters for smoothing out noisy values before they are shown
+
+use std::collections::VecDeque;
+
+/// How a gauge filters its value before showing it, so that noise doesn't make the indicator
+/// vibrate. The filter is applied once per frame.
+#[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Smoothing {
+    /// An exponential moving average, where each frame the shown value moves the given fraction
+    /// (from 0 to 1) of the way to the value. Smaller fractions filter more.
+    Ema(f64),
+    /// The average of the values from the given number of most recent frames
+    MovingAverage(usize),
+}
+
+/// The history a filter needs, kept between frames
+#[derive(Clone, Default)]
+pub(crate) struct SmoothingState {
+    average: Option<f64>,
+    window: VecDeque<f64>,
+}
+
+impl SmoothingState {
+    /// Filter the value for this frame
+    pub(crate) fn update(&mut self, value: f64, smoothing: Smoothing) -> f64 {
+        match smoothing {
+            Smoothing::Ema(alpha) => {
+                let average = self.average.get_or_insert(value);
+                *average += (value - *average) * alpha.clamp(0.0, 1.0);
+                *average
+            }
+            Smoothing::MovingAverage(len) => {
+                self.window.push_back(value);
+                while self.window.len() > len.max(1) {
+                    self.window.pop_front();
+                }
+                self.window.iter().sum::<f64>() / self.window.len() as f64
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Sources of values, for plugging sensors and generators into gauges
+
+use std::ops::RangeInclusive;
+
+/// Something which produces the values shown by a gauge, like an adapter for a sensor, a ring
+/// buffer of readings or a generator of test values. A source is sampled once per frame.
+///
+/// Closures returning an `f64` are sources too. Since a source only produces values, values
+/// sampled from it can be shown on any of the widgets in this crate.
+pub trait GaugeSource {
+    /// The current value
+    fn sample(&mut self) -> f64;
+
+    /// The range of values the source produces, if it is known. Gauges created from the source
+    /// use it as their range.
+    fn range_hint(&self) -> Option<RangeInclusive<f64>> {
+        None
+    }
+}
+
+impl<F: FnMut() -> f64> GaugeSource for F {
+    fn sample(&mut self) -> f64 {
+        self()
+    }
+}
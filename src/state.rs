@@ -0,0 +1,434 @@
+//! Runtime state for gauges, kept in egui's memory between frames
+
+use crate::animation::{Animation, SpringState};
+use crate::interpolation::InterpolationState;
+use crate::smoothing::SmoothingState;
+use crate::style::GaugeStyle;
+use crate::zone::Zone;
+use egui::util::IdTypeMap;
+use egui::{Context, Id, Shape};
+use epaint::mutex::Mutex;
+use epaint::TextureAtlas;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Weak};
+
+/// How far back (in seconds) samples are kept to compute the rate of change
+pub(crate) const RATE_WINDOW: f64 = 1.0;
+
+/// Runtime state for a gauge which needs to be remembered between frames
+#[derive(Clone, Default)]
+pub(crate) struct GaugeState {
+    /// (time, value) pairs, oldest first
+    pub(crate) samples: VecDeque<(f64, f64)>,
+    /// Recent distinct values for the sparkline, oldest first
+    pub(crate) history: VecDeque<f64>,
+    /// The unsnapped value while the gauge is being dragged
+    pub(crate) drag_value: Option<f64>,
+    pub(crate) animation: Option<Animation>,
+    pub(crate) spring: Option<SpringState>,
+    /// The value from the previous frame
+    pub(crate) last_value: Option<f64>,
+    /// When the center value was last highlighted for a large change
+    pub(crate) flash_time: Option<f64>,
+    /// Recent positions of the indicator, oldest first
+    pub(crate) trail: VecDeque<f64>,
+    pub(crate) smoothing: SmoothingState,
+    pub(crate) interpolation: InterpolationState,
+    /// The last value which wasn't NaN or infinite, for showing it while there is no data
+    pub(crate) last_valid_value: Option<f64>,
+    /// The zone the value was in on the last frame, once there has been one
+    pub(crate) zone: Option<Option<Zone>>,
+    /// The index of the zone the value is in, taking hysteresis into account
+    pub(crate) zone_index: Option<usize>,
+    /// What was last announced to screen readers about the value entering or leaving an alarm
+    pub(crate) announcement: Option<String>,
+    /// The list the shapes of a gauge which paints again every frame are collected in, kept empty
+    /// between frames for its capacity. The points of the shapes can't be kept along with it,
+    /// since epaint takes them over with the shapes.
+    pub(crate) shapes: Vec<Shape>,
+}
+
+impl GaugeState {
+    pub(crate) fn record_sample(&mut self, time: f64, value: f64) {
+        if self.samples.back().is_some_and(|&(t, _)| t == time) {
+            self.samples.pop_back();
+        }
+        self.samples.push_back((time, value));
+        while self.samples.len() > 2 && time - self.samples[1].0 > RATE_WINDOW {
+            self.samples.pop_front();
+        }
+    }
+
+    pub(crate) fn record_history(&mut self, value: f64, len: usize) {
+        if self.history.back() != Some(&value) {
+            self.history.push_back(value);
+        }
+        while self.history.len() > len {
+            self.history.pop_front();
+        }
+    }
+
+    pub(crate) fn record_trail(&mut self, value: f64, len: usize) {
+        if self.trail.back() != Some(&value) {
+            self.trail.push_back(value);
+        }
+        while self.trail.len() > len {
+            self.trail.pop_front();
+        }
+    }
+
+    /// The change in value per second over the sample history
+    pub(crate) fn rate(&self) -> f64 {
+        match (self.samples.front(), self.samples.back()) {
+            (Some(&(t0, v0)), Some(&(t1, v1))) if t1 > t0 => (v1 - v0) / (t1 - t0),
+            _ => 0.0,
+        }
+    }
+}
+
+/// The value under the pointer on whichever gauge of a link group is hovered, kept in egui's
+/// memory under the id of the group
+#[derive(Clone, Copy)]
+pub(crate) struct LinkedHover {
+    pub(crate) value: f64,
+    /// The id of the gauge which is hovered
+    pub(crate) gauge: Id,
+    /// The frame the gauge was hovered on, so that gauges painted before it on the next frame
+    /// still see it
+    pub(crate) frame: u64,
+}
+
+/// Runtime state for a gauge which is worth keeping across restarts of the app. It is only
+/// actually saved when the `persistence` feature is enabled.
+#[derive(Clone, Default)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct PersistedState {
+    /// The highest value seen, and when it was last updated
+    pub(crate) peak: Option<(f64, f64)>,
+    /// The lowest and highest values seen since the watermarks were reset
+    pub(crate) watermarks: Option<(f64, f64)>,
+    /// Whether the value entered a critical zone since the alarm was last acknowledged
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub(crate) alarm_latched: bool,
+    /// The running statistics of the values since they were reset
+    #[cfg_attr(feature = "persistence", serde(default))]
+    pub(crate) statistics: Option<Statistics>,
+}
+
+/// The lowest, highest and average of the values a gauge was shown with
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "persistence", derive(serde::Deserialize, serde::Serialize))]
+pub(crate) struct Statistics {
+    pub(crate) min: f64,
+    pub(crate) max: f64,
+    sum: f64,
+    count: u64,
+}
+
+impl Statistics {
+    pub(crate) fn mean(&self) -> f64 {
+        self.sum / self.count as f64
+    }
+}
+
+impl PersistedState {
+    /// Get the state for the given gauge, which is persisted if possible
+    pub(crate) fn get(data: &mut IdTypeMap, id: Id) -> &mut Self {
+        #[cfg(feature = "persistence")]
+        return data.get_persisted_mut_or_default(id);
+        #[cfg(not(feature = "persistence"))]
+        return data.get_temp_mut_or_default(id);
+    }
+
+    /// Raise the peak to the given value if it's higher, otherwise let it fall at the given rate
+    pub(crate) fn update_peak(&mut self, value: f64, time: f64, decay: f64) -> f64 {
+        let peak = match self.peak {
+            // the time is reset when the app restarts, so it can appear to go backwards
+            Some((peak, peak_time)) => (peak - decay * (time - peak_time).max(0.0)).max(value),
+            None => value,
+        };
+        self.peak = Some((peak, time));
+        peak
+    }
+
+    pub(crate) fn update_watermarks(&mut self, value: f64) {
+        let (low, high) = self.watermarks.get_or_insert((value, value));
+        *low = low.min(value);
+        *high = high.max(value);
+    }
+
+    /// Add the value of this frame to the statistics
+    pub(crate) fn update_statistics(&mut self, value: f64) {
+        let statistics = self.statistics.get_or_insert(Statistics {
+            min: value,
+            max: value,
+            sum: 0.0,
+            count: 0,
+        });
+        statistics.min = statistics.min.min(value);
+        statistics.max = statistics.max.max(value);
+        statistics.sum += value;
+        statistics.count += 1;
+    }
+}
+
+/// The shapes a gauge was last painted with, which are painted again for as long as nothing which
+/// affects them changes
+#[derive(Clone)]
+pub(crate) struct ShapeCache {
+    /// A hash of everything the shapes were painted from
+    pub(crate) key: u64,
+    pub(crate) shapes: Arc<[Shape]>,
+    /// The font atlas the text in the shapes was laid out on. egui starts over with new fonts and
+    /// a new atlas when it rebuilds its fonts, which it does as the atlas fills up, when the scale
+    /// changes or when the fonts change, and the galleys from before point at glyphs which aren't
+    /// in the atlas anymore. Holding on to the atlas weakly keeps a new one from ending up at the
+    /// same address.
+    atlas: Weak<Mutex<TextureAtlas>>,
+}
+
+impl ShapeCache {
+    /// The key for the given inputs to painting
+    pub(crate) fn key(inputs: &impl Hash) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        inputs.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    /// The shapes cached under the given id if they were painted from inputs with the given key,
+    /// or else the shapes from painting them again, which are cached in their place
+    pub(crate) fn get_or_paint(
+        ctx: &Context,
+        id: Id,
+        key: u64,
+        paint: impl FnOnce() -> Vec<Shape>,
+    ) -> Arc<[Shape]> {
+        let atlas = ctx.fonts(|fonts| Arc::downgrade(&fonts.texture_atlas()));
+        let cached = ctx
+            .data(|d| d.get_temp::<Self>(id))
+            .filter(|cache| cache.key == key && cache.atlas.ptr_eq(&atlas));
+        if let Some(cache) = cached {
+            return cache.shapes;
+        }
+        let shapes: Arc<[Shape]> = paint().into();
+        ctx.data_mut(|d| {
+            d.insert_temp(
+                id,
+                Self {
+                    key,
+                    shapes: shapes.clone(),
+                    atlas,
+                },
+            )
+        });
+        shapes
+    }
+}
+
+/// Hashing for the inputs shapes are cached by. Unlike `Hash` it covers floats, by their bits,
+/// which only ever leads to painting again when it wasn't needed.
+pub(crate) trait KeyHash {
+    fn key_hash<H: Hasher>(&self, state: &mut H);
+}
+
+/// Gives anything with a [`KeyHash`] a `Hash`, for keys which derive it
+pub(crate) struct Keyed<T>(pub(crate) T);
+
+impl<T: KeyHash> Hash for Keyed<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.key_hash(state);
+    }
+}
+
+macro_rules! key_hash_by_hash {
+    ($($t:ty),*) => {
+        $(impl KeyHash for $t {
+            fn key_hash<H: Hasher>(&self, state: &mut H) {
+                self.hash(state);
+            }
+        })*
+    };
+}
+
+key_hash_by_hash!(
+    bool,
+    usize,
+    str,
+    String,
+    epaint::Color32,
+    egui::FontId,
+    crate::Dial,
+    crate::MarkerStyle,
+    crate::OverflowBehavior,
+    crate::Palette,
+    crate::Severity,
+    crate::scale::Labels
+);
+
+impl KeyHash for f32 {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u32(self.to_bits());
+    }
+}
+
+impl KeyHash for f64 {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.to_bits());
+    }
+}
+
+impl<T: KeyHash + ?Sized> KeyHash for &T {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        (**self).key_hash(state);
+    }
+}
+
+impl<T: KeyHash> KeyHash for Option<T> {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.is_some().hash(state);
+        if let Some(value) = self {
+            value.key_hash(state);
+        }
+    }
+}
+
+impl<T: KeyHash> KeyHash for [T] {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.len().hash(state);
+        for value in self {
+            value.key_hash(state);
+        }
+    }
+}
+
+impl<T: KeyHash, const N: usize> KeyHash for [T; N] {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().key_hash(state);
+    }
+}
+
+impl<T: KeyHash> KeyHash for Vec<T> {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.as_slice().key_hash(state);
+    }
+}
+
+impl<A: KeyHash, B: KeyHash> KeyHash for (A, B) {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.key_hash(state);
+        self.1.key_hash(state);
+    }
+}
+
+impl<A: KeyHash, B: KeyHash, C: KeyHash> KeyHash for (A, B, C) {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        self.0.key_hash(state);
+        self.1.key_hash(state);
+        self.2.key_hash(state);
+    }
+}
+
+impl KeyHash for egui::Rect {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        [self.min.x, self.min.y, self.max.x, self.max.y].key_hash(state);
+    }
+}
+
+impl KeyHash for Zone {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        (*self.range.start(), *self.range.end(), self.hysteresis).key_hash(state);
+        (self.color, self.severity, &self.name).key_hash(state);
+        self.text_color.key_hash(state);
+    }
+}
+
+impl KeyHash for GaugeStyle {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        let GaugeStyle {
+            track_color,
+            text_color,
+            knob_color,
+            redline_color,
+            debug_color,
+            palette,
+            zone_patterns,
+            high_contrast,
+            brightness,
+            skirt_font,
+            skirt_color,
+            thickness,
+        } = self;
+        [
+            track_color,
+            text_color,
+            knob_color,
+            redline_color,
+            debug_color,
+        ]
+        .key_hash(state);
+        (palette, zone_patterns, high_contrast).key_hash(state);
+        (brightness, skirt_font, skirt_color).key_hash(state);
+        thickness.key_hash(state);
+    }
+}
+
+impl KeyHash for Statistics {
+    fn key_hash<H: Hasher>(&self, state: &mut H) {
+        [self.min, self.max, self.sum].key_hash(state);
+        self.count.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::{FontDefinitions, FontFamily, FontId, Pos2, RawInput};
+    use epaint::Color32;
+
+    /// Paints some text through the cache for a frame, returning whether it had to be painted
+    fn paint_frame(ctx: &Context, key: u64) -> bool {
+        let mut painted = false;
+        let _ = ctx.run(RawInput::default(), |ctx| {
+            ShapeCache::get_or_paint(ctx, Id::new("cache"), key, || {
+                painted = true;
+                let galley = ctx.fonts(|fonts| {
+                    fonts.layout_no_wrap("42".into(), FontId::monospace(12.0), Color32::WHITE)
+                });
+                vec![Shape::galley(Pos2::ZERO, galley, Color32::WHITE)]
+            });
+        });
+        painted
+    }
+
+    #[test]
+    fn shapes_are_reused_while_the_key_stays_the_same() {
+        let ctx = Context::default();
+        assert!(paint_frame(&ctx, 1));
+        assert!(!paint_frame(&ctx, 1));
+        assert!(paint_frame(&ctx, 2));
+    }
+
+    #[test]
+    fn text_is_laid_out_again_when_the_fonts_change() {
+        let ctx = Context::default();
+        assert!(paint_frame(&ctx, 1));
+        assert!(!paint_frame(&ctx, 1));
+        let mut fonts = FontDefinitions::default();
+        if let Some(fonts) = fonts.families.get_mut(&FontFamily::Monospace) {
+            fonts.reverse();
+        }
+        ctx.set_fonts(fonts);
+        assert!(paint_frame(&ctx, 1));
+        assert!(!paint_frame(&ctx, 1));
+    }
+
+    #[test]
+    fn text_is_laid_out_again_when_the_scale_changes() {
+        let ctx = Context::default();
+        assert!(paint_frame(&ctx, 1));
+        ctx.set_pixels_per_point(2.0);
+        assert!(paint_frame(&ctx, 1));
+    }
+}
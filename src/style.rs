@@ -0,0 +1,247 @@
+//! Appearance of gauges which isn't tied to what they show, so that it can be shared and loaded
+//! from theme files
+
+use crate::zone::Severity;
+use egui::{Context, FontId, Id};
+use epaint::Color32;
+
+/// Colors which replace the color of a gauge and of its zones, so that a set of gauges can be
+/// switched to colors that stay distinguishable for people with color vision deficiencies. Zones
+/// get the color for their [`Severity`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Palette {
+    /// The color of the value, in place of the color the gauge was created with
+    pub accent: Color32,
+    /// The color of zones which are neither warnings nor critical
+    pub normal: Color32,
+    /// The color of warning zones
+    pub warning: Color32,
+    /// The color of critical zones
+    pub critical: Color32,
+}
+
+impl Palette {
+    /// The palette of Masataka Okabe and Kei Ito, which stays distinguishable with deuteranopia
+    /// and protanopia (red-green color blindness): blue, bluish green, orange and vermillion
+    pub const OKABE_ITO: Self = Self {
+        accent: Color32::from_rgb(0, 114, 178),
+        normal: Color32::from_rgb(0, 158, 115),
+        warning: Color32::from_rgb(230, 159, 0),
+        critical: Color32::from_rgb(213, 94, 0),
+    };
+
+    /// The color blind safe palette of the IBM Design Language, which relies on blue against
+    /// yellow and magenta rather than on red against green: blue, purple, yellow and magenta
+    pub const IBM: Self = Self {
+        accent: Color32::from_rgb(100, 143, 255),
+        normal: Color32::from_rgb(120, 94, 240),
+        warning: Color32::from_rgb(255, 176, 0),
+        critical: Color32::from_rgb(220, 38, 127),
+    };
+
+    /// The color for zones of the given severity
+    pub fn zone_color(&self, severity: Severity) -> Color32 {
+        match severity {
+            Severity::Normal => self.normal,
+            Severity::Warning => self.warning,
+            Severity::Critical => self.critical,
+        }
+    }
+}
+
+/// Colors and proportions for a [`Gauge`](crate::Gauge), applied with
+/// [`Gauge::style`](crate::Gauge::style). Anything left as `None` comes from the egui style of the
+/// `Ui` the gauge is shown in.
+///
+/// With the `serde` feature a style can be stored in config files, and any missing keys are left
+/// as `None`. With the `toml` feature it can be loaded with [`Self::from_toml`] from a theme like
+/// this, and with the `json` feature with [`Self::from_json`] from the same keys in JSON:
+///
+/// ```toml
+/// track_color = [60, 60, 60, 255]
+/// text_color = [220, 220, 220, 255]
+/// high_contrast = false
+/// brightness = 0.8
+/// thickness = 0.1
+/// skirt_font = { size = 10.0, family = "Monospace" }
+///
+/// [palette]
+/// accent = [0, 114, 178, 255]
+/// normal = [0, 158, 115, 255]
+/// warning = [230, 159, 0, 255]
+/// critical = [213, 94, 0, 255]
+/// ```
+///
+/// The keys are the names of the fields below, and unknown keys are an error so that typos don't
+/// go unnoticed. Colors (`track_color`, `text_color`, `knob_color`, `redline_color`,
+/// `debug_color`, `skirt_color` and the four colors of the `palette`) are arrays of the red,
+/// green, blue and alpha components from 0 to 255, premultiplied by alpha, so a half transparent
+/// white is `[128, 128, 128, 128]`. `zone_patterns` and `high_contrast` are booleans, and
+/// `brightness` and `thickness` are numbers. `skirt_font` has a `size` in points and a `family`
+/// of `"Proportional"`, `"Monospace"` or `{ Name = "..." }` for a font added to egui.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct GaugeStyle {
+    /// The color of the arc behind the value. Defaults to the color of the rail of a slider.
+    pub track_color: Option<Color32>,
+    /// The color of the value, labels, ticks and text
+    pub text_color: Option<Color32>,
+    /// The fill of the knob at the end of the value arc. Defaults to the background color of
+    /// text edits.
+    pub knob_color: Option<Color32>,
+    /// The color of the [`Gauge::redline`](crate::Gauge::redline). Defaults to the error color of
+    /// the egui visuals.
+    pub redline_color: Option<Color32>,
+    /// The color of the overlay drawn by [`Gauge::debug`](crate::Gauge::debug). Defaults to
+    /// magenta, which stands out against any theme.
+    pub debug_color: Option<Color32>,
+    /// Colors to use in place of the color of the gauge and its zones, like
+    /// [`Palette::OKABE_ITO`] for color blind users
+    pub palette: Option<Palette>,
+    /// Draw the bands of zones with a pattern for their severity, so that they differ in more
+    /// than color: solid for normal zones, dashed for warnings and dotted for critical zones
+    pub zone_patterns: Option<bool>,
+    /// Draw for control room displays viewed from a distance and for low vision users: a pure
+    /// black or white background (following whether the visuals are dark), text in the opposite
+    /// color, thicker arcs and lines and larger fonts for small labels. Set it on the
+    /// [`GaugeTheme`] to turn it on for every gauge of the app.
+    pub high_contrast: Option<bool>,
+    /// How bright the gauge is, from 0 for black to 1 for its normal colors, for night modes
+    /// where the whole instrument panel dims together. Set it on the [`GaugeTheme`] to dim every
+    /// gauge of the app. Defaults to 1.
+    pub brightness: Option<f32>,
+    /// The font of the [`Gauge::skirt_text`](crate::Gauge::skirt_text). Defaults to a small
+    /// monospace font in proportion to the gauge.
+    pub skirt_font: Option<FontId>,
+    /// The color of the [`Gauge::skirt_text`](crate::Gauge::skirt_text). Defaults to the text
+    /// color.
+    pub skirt_color: Option<Color32>,
+    /// The width of the arc, as a fraction of the width of the gauge. Defaults to 1/15.
+    pub thickness: Option<f32>,
+}
+
+impl GaugeStyle {
+    /// Fill in whatever this style leaves as `None` from the given style
+    pub(crate) fn or(self, fallback: &GaugeStyle) -> Self {
+        Self {
+            track_color: self.track_color.or(fallback.track_color),
+            text_color: self.text_color.or(fallback.text_color),
+            knob_color: self.knob_color.or(fallback.knob_color),
+            redline_color: self.redline_color.or(fallback.redline_color),
+            debug_color: self.debug_color.or(fallback.debug_color),
+            palette: self.palette.or(fallback.palette),
+            zone_patterns: self.zone_patterns.or(fallback.zone_patterns),
+            high_contrast: self.high_contrast.or(fallback.high_contrast),
+            brightness: self.brightness.or(fallback.brightness),
+            skirt_font: self.skirt_font.clone().or(fallback.skirt_font.clone()),
+            skirt_color: self.skirt_color.or(fallback.skirt_color),
+            thickness: self.thickness.or(fallback.thickness),
+        }
+    }
+
+    /// Load a style from a TOML theme, see [`GaugeStyle`] for the keys
+    #[cfg(feature = "toml")]
+    pub fn from_toml(toml: &str) -> Result<Self, toml_edit::de::Error> {
+        toml_edit::de::from_str(toml)
+    }
+
+    /// Load a style from a JSON theme, with the same keys as a TOML one, see [`GaugeStyle`]
+    #[cfg(feature = "json")]
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+/// Where the theme is kept in the data of the context, away from the data of other crates
+fn theme_id() -> Id {
+    Id::new("egui_gauge::theme")
+}
+
+/// The default styles of the gauges of an app, which is stored in the `Context` so that it
+/// doesn't have to be passed to every gauge. Anything a gauge's own style sets takes precedence
+/// over the theme. The other widgets of this crate, which don't have a style of their own, take
+/// the track and text colors of the [`Self::gauge`] style.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct GaugeTheme {
+    /// The style of every [`Gauge`](crate::Gauge), whose track and text colors are also used by
+    /// the other widgets
+    pub gauge: GaugeStyle,
+}
+
+impl GaugeTheme {
+    /// Make the given theme the default for all the gauges shown with the given context
+    pub fn install(ctx: &Context, theme: GaugeTheme) {
+        ctx.data_mut(|d| d.insert_temp(theme_id(), theme));
+    }
+
+    /// The theme installed in the given context, or the default one
+    pub fn get(ctx: &Context) -> GaugeTheme {
+        ctx.data(|d| d.get_temp(theme_id())).unwrap_or_default()
+    }
+}
+
+#[cfg(all(test, any(feature = "toml", feature = "json")))]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn documented_toml_theme_loads() {
+        let style = GaugeStyle::from_toml(
+            r#"
+            track_color = [60, 60, 60, 255]
+            text_color = [220, 220, 220, 255]
+            high_contrast = false
+            brightness = 0.8
+            thickness = 0.1
+            skirt_font = { size = 10.0, family = "Monospace" }
+
+            [palette]
+            accent = [0, 114, 178, 255]
+            normal = [0, 158, 115, 255]
+            warning = [230, 159, 0, 255]
+            critical = [213, 94, 0, 255]
+            "#,
+        )
+        .unwrap();
+        assert_eq!(style.track_color, Some(Color32::from_gray(60)));
+        assert_eq!(style.palette, Some(Palette::OKABE_ITO));
+        assert_eq!(style.skirt_font, Some(FontId::monospace(10.0)));
+        assert_eq!(style.knob_color, None);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_theme_loads() {
+        let style = GaugeStyle::from_json(
+            r#"{
+                "text_color": [220, 220, 220, 255],
+                "thickness": 0.1,
+                "skirt_font": { "size": 10.0, "family": { "Name": "gauge" } }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(style.text_color, Some(Color32::from_gray(220)));
+        assert_eq!(style.thickness, Some(0.1));
+        assert_eq!(
+            style.skirt_font,
+            Some(FontId::new(10.0, egui::FontFamily::Name("gauge".into())))
+        );
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn unknown_keys_are_an_error() {
+        assert!(GaugeStyle::from_json(r#"{ "track_colour": [0, 0, 0, 255] }"#).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Exporting gauges as SVG, for embedding readings in reports at full vector quality
+
+use egui::epaint::{Mesh, PathShape, RectShape, TextShape};
+use egui::{Pos2, Shape, Vec2};
+use epaint::{Color32, Stroke};
+use std::fmt::Write;
+
+/// Write the given shapes, which fit in a rect of the given size at the origin, as an SVG
+/// document. Lines and arcs become paths, and text becomes text elements.
+pub(crate) fn to_svg(shapes: &[Shape], size: Vec2) -> String {
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+         viewBox=\"0 0 {w} {h}\">\n",
+        w = size.x,
+        h = size.y
+    );
+    for shape in shapes {
+        write_shape(&mut svg, shape);
+    }
+    svg += "</svg>\n";
+    svg
+}
+
+/// The fill or stroke attributes for the given color, which has premultiplied alpha in egui
+fn paint(attribute: &str, color: Color32) -> String {
+    if color.a() == 0 {
+        return format!(" {attribute}=\"none\"");
+    }
+    let [r, g, b, a] = color.to_srgba_unmultiplied();
+    let mut paint = format!(" {attribute}=\"#{r:02x}{g:02x}{b:02x}\"");
+    if a < 255 {
+        let _ = write!(paint, " {attribute}-opacity=\"{:.3}\"", a as f32 / 255.0);
+    }
+    paint
+}
+
+fn stroke(stroke: Stroke) -> String {
+    if stroke.is_empty() {
+        return " stroke=\"none\"".into();
+    }
+    format!(
+        "{} stroke-width=\"{}\"",
+        paint("stroke", stroke.color),
+        stroke.width
+    )
+}
+
+fn points(points: &[Pos2]) -> String {
+    let mut d = String::new();
+    for (i, p) in points.iter().enumerate() {
+        let command = if i == 0 { 'M' } else { 'L' };
+        let _ = write!(d, "{command}{:.2} {:.2} ", p.x, p.y);
+    }
+    d.trim_end().into()
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_shape(svg: &mut String, shape: &Shape) {
+    match shape {
+        Shape::Noop | Shape::Callback(_) => {}
+        Shape::Vec(shapes) => {
+            for shape in shapes {
+                write_shape(svg, shape);
+            }
+        }
+        Shape::Circle(circle) => {
+            let _ = writeln!(
+                svg,
+                "<circle cx=\"{}\" cy=\"{}\" r=\"{}\"{}{}/>",
+                circle.center.x,
+                circle.center.y,
+                circle.radius,
+                paint("fill", circle.fill),
+                stroke(circle.stroke)
+            );
+        }
+        Shape::Ellipse(ellipse) => {
+            let _ = writeln!(
+                svg,
+                "<ellipse cx=\"{}\" cy=\"{}\" rx=\"{}\" ry=\"{}\"{}{}/>",
+                ellipse.center.x,
+                ellipse.center.y,
+                ellipse.radius.x,
+                ellipse.radius.y,
+                paint("fill", ellipse.fill),
+                stroke(ellipse.stroke)
+            );
+        }
+        Shape::LineSegment {
+            points: [a, b],
+            stroke: s,
+        } => {
+            let _ = writeln!(
+                svg,
+                "<line x1=\"{}\" y1=\"{}\" x2=\"{}\" y2=\"{}\"{}/>",
+                a.x,
+                a.y,
+                b.x,
+                b.y,
+                stroke(*s)
+            );
+        }
+        Shape::Path(PathShape {
+            points: path,
+            closed,
+            fill,
+            stroke: s,
+        }) => {
+            let close = if *closed { " Z" } else { "" };
+            let _ = writeln!(
+                svg,
+                "<path d=\"{}{close}\"{}{}/>",
+                points(path),
+                paint("fill", *fill),
+                stroke(*s)
+            );
+        }
+        Shape::Rect(RectShape {
+            rect,
+            rounding,
+            fill,
+            stroke: s,
+            ..
+        }) => {
+            let _ = writeln!(
+                svg,
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" rx=\"{}\"{}{}/>",
+                rect.min.x,
+                rect.min.y,
+                rect.width(),
+                rect.height(),
+                rounding.nw,
+                paint("fill", *fill),
+                stroke(*s)
+            );
+        }
+        Shape::Text(text) => write_text(svg, text),
+        Shape::Mesh(mesh) => write_mesh(svg, mesh),
+        Shape::QuadraticBezier(curve) => {
+            let [a, b, c] = curve.points;
+            let close = if curve.closed { " Z" } else { "" };
+            let _ = writeln!(
+                svg,
+                "<path d=\"M{} {} Q{} {} {} {}{close}\"{}{}/>",
+                a.x,
+                a.y,
+                b.x,
+                b.y,
+                c.x,
+                c.y,
+                paint("fill", curve.fill),
+                stroke(curve.stroke)
+            );
+        }
+        Shape::CubicBezier(curve) => {
+            let [a, b, c, d] = curve.points;
+            let close = if curve.closed { " Z" } else { "" };
+            let _ = writeln!(
+                svg,
+                "<path d=\"M{} {} C{} {} {} {} {} {}{close}\"{}{}/>",
+                a.x,
+                a.y,
+                b.x,
+                b.y,
+                c.x,
+                c.y,
+                d.x,
+                d.y,
+                paint("fill", curve.fill),
+                stroke(curve.stroke)
+            );
+        }
+    }
+}
+
+/// Write every row of the text as a text element on its baseline, stretched to the width egui
+/// laid it out with, so that it lines up even where the monospace font of the viewer differs
+fn write_text(svg: &mut String, text: &TextShape) {
+    for row in &text.galley.rows {
+        let (Some(first), Some(last)) = (row.glyphs.first(), row.glyphs.last()) else {
+            continue;
+        };
+        let format = &text.galley.job.sections[first.section_index as usize].format;
+        let color = text
+            .override_text_color
+            .unwrap_or(if format.color == Color32::PLACEHOLDER {
+                text.fallback_color
+            } else {
+                format.color
+            });
+        let _ = writeln!(
+            svg,
+            "<text x=\"{}\" y=\"{}\" font-family=\"monospace\" font-size=\"{}\" \
+             textLength=\"{}\" lengthAdjust=\"spacingAndGlyphs\"{}>{}</text>",
+            text.pos.x + first.pos.x,
+            text.pos.y + first.pos.y,
+            format.font_id.size,
+            last.max_x() - first.pos.x,
+            paint("fill", color.gamma_multiply(text.opacity_factor)),
+            escape(&row.text())
+        );
+    }
+}
+
+/// Write the triangles of the mesh, each in the color of its first vertex
+fn write_mesh(svg: &mut String, mesh: &Mesh) {
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, b, c] = [0, 1, 2].map(|i| &mesh.vertices[triangle[i] as usize]);
+        let _ = writeln!(
+            svg,
+            "<path d=\"{} Z\"{}/>",
+            points(&[a.pos, b.pos, c.pos]),
+            paint("fill", a.color)
+        );
+    }
+}
@@ -0,0 +1,228 @@
+//! A gauge for engine speeds, with a needle and a redline
+
+use crate::{arc_points, polar, themed_text_color, themed_track_color, Dial};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// The most steps there are between labels along the scale. With more thousands than that, the
+/// labels are a few thousands apart.
+const MAX_LABELS: u64 = 12;
+
+/// A tachometer showing engine speed in revolutions per minute. The scale is labelled in
+/// thousands, and the speeds above the redline are shaded red.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tachometer {
+    rpm: f64,
+    max_rpm: f64,
+    size: f32,
+    color: Color32,
+    redline: Option<f64>,
+}
+
+impl Tachometer {
+    /// Create a tachometer showing the given engine speed on a scale from 0 to the given maximum,
+    /// which is rounded up to a whole thousand. The given size is the width and height of the
+    /// tachometer. The given color is the color of the needle.
+    pub fn new(
+        rpm: impl emath::Numeric,
+        max_rpm: impl emath::Numeric,
+        size: f32,
+        color: Color32,
+    ) -> Self {
+        Self {
+            rpm: rpm.to_f64(),
+            max_rpm: (max_rpm.to_f64() / 1000.0).ceil().max(1.0) * 1000.0,
+            size,
+            color,
+            redline: None,
+        }
+    }
+
+    /// Shade the scale red from the given engine speed up to the maximum
+    pub fn redline(mut self, rpm: impl emath::Numeric) -> Self {
+        self.redline = Some(rpm.to_f64());
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 * 0.95
+    }
+
+    fn font_size(&self) -> f32 {
+        self.size / 12.0
+    }
+
+    fn rpm_to_angle(&self, rpm: f64) -> f32 {
+        let dial = Dial::ThreeQuarter;
+        let fraction = (rpm / self.max_rpm).clamp(0.0, 1.0);
+        dial.start_angle() as f32 - dial.sweep() as f32 * fraction as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+
+        self.paint_arc(
+            ui,
+            center,
+            0.0,
+            self.max_rpm,
+            self.radius(),
+            Stroke {
+                width: self.size / 100.0,
+                color: themed_track_color(ui),
+            },
+        );
+        if let Some(redline) = self.redline {
+            let width = self.size / 25.0;
+            self.paint_arc(
+                ui,
+                center,
+                redline,
+                self.max_rpm,
+                self.radius() - width / 2.0,
+                Stroke {
+                    width,
+                    color: Color32::RED.gamma_multiply(0.7),
+                },
+            );
+        }
+        self.paint_scale(ui, center, text_color);
+        self.write_rpm(ui, center, text_color);
+        self.paint_needle(ui, center);
+    }
+
+    fn paint_arc(
+        &mut self,
+        ui: &mut Ui,
+        center: Pos2,
+        from: f64,
+        to: f64,
+        radius: f32,
+        stroke: Stroke,
+    ) {
+        let from = self.rpm_to_angle(from);
+        let to = self.rpm_to_angle(to);
+        if to >= from {
+            return;
+        }
+        ui.painter()
+            .add(Shape::line(arc_points(center, radius, to, from), stroke));
+    }
+
+    /// How many thousands apart the labels are: every thousand, or as few more as it takes to
+    /// keep to a readable number of labels, as 2, 5, 10, 20 and so on
+    fn label_step(thousands: u64) -> u64 {
+        let mut magnitude = 1;
+        loop {
+            for step in [magnitude, magnitude * 2, magnitude * 5] {
+                if thousands / step <= MAX_LABELS {
+                    return step;
+                }
+            }
+            magnitude *= 10;
+        }
+    }
+
+    fn paint_scale(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        let thousands = (self.max_rpm / 1000.0) as u64;
+        let step = Self::label_step(thousands);
+        for half_steps in 0..=thousands / step * 2 {
+            let rpm = half_steps as f64 * step as f64 * 500.0;
+            let angle = self.rpm_to_angle(rpm);
+            let major = half_steps % 2 == 0;
+            let length = if major { 0.12 } else { 0.06 };
+            ui.painter().line_segment(
+                [
+                    polar(center, angle, self.radius()),
+                    polar(center, angle, self.radius() * (1.0 - length)),
+                ],
+                Stroke {
+                    width: if major { 2.0 } else { 1.0 },
+                    color: text_color,
+                },
+            );
+            if major {
+                ui.painter().text(
+                    polar(center, angle, self.radius() * 0.75),
+                    Align2::CENTER_CENTER,
+                    (half_steps / 2 * step).to_string(),
+                    FontId {
+                        size: self.font_size(),
+                        family: FontFamily::Monospace,
+                    },
+                    text_color,
+                );
+            }
+        }
+    }
+
+    fn write_rpm(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.35,
+            },
+            Align2::CENTER_CENTER,
+            format!("{:.0}", self.rpm),
+            FontId {
+                size: self.font_size(),
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.55,
+            },
+            Align2::CENTER_CENTER,
+            "x1000 rpm",
+            FontId {
+                size: self.font_size() * 0.6,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn paint_needle(&mut self, ui: &mut Ui, center: Pos2) {
+        let angle = self.rpm_to_angle(self.rpm);
+        ui.painter().line_segment(
+            [
+                polar(center, angle + 180.0, self.radius() * 0.15),
+                polar(center, angle, self.radius() * 0.95),
+            ],
+            Stroke {
+                width: self.size / 80.0,
+                color: self.color,
+            },
+        );
+        ui.painter()
+            .circle_filled(center, self.size / 25.0, themed_track_color(ui));
+        ui.painter()
+            .circle_filled(center, self.size / 60.0, self.color);
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.rpm),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Tachometer {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,283 @@
+//! A vertical gauge which looks like a thermometer
+
+use crate::scale::{self, Scale};
+use crate::zone::{self, Zone};
+use crate::{is_valid_range, ordered_range, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Ui};
+use epaint::{Color32, Pos2, Stroke};
+use std::ops::RangeInclusive;
+
+/// A vertical gauge styled like a thermometer, with a bulb at the bottom and graduations along the
+/// side of its tube. Useful for temperatures and tank levels.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Thermometer {
+    value: f64,
+    min_value: f64,
+    max_value: f64,
+    height: f32,
+    color: Color32,
+    text: String,
+    labels: scale::Labels,
+    zones: Vec<Zone>,
+}
+
+impl Thermometer {
+    /// Create a thermometer which displays the given value as part of the given range. The given
+    /// height is the height of the thermometer, and its width is proportional to it. The given
+    /// color is the color of the liquid in the bulb and tube. Reversed ranges work like their
+    /// normal counterparts, and empty or infinite ranges show an empty, greyed out thermometer.
+    pub fn new<Num: emath::Numeric>(
+        value: Num,
+        range: RangeInclusive<Num>,
+        height: f32,
+        color: Color32,
+    ) -> Self {
+        let (min_value, max_value) = ordered_range(&range);
+        Self {
+            value: value.to_f64(),
+            min_value,
+            max_value,
+            height,
+            color,
+            text: Default::default(),
+            labels: Default::default(),
+            zones: Vec::new(),
+        }
+    }
+
+    /// Text to be displayed under the bulb
+    pub fn text(mut self, text: impl Into<String>) -> Self {
+        self.text = text.into();
+        self
+    }
+
+    /// Add a zone to the thermometer. When zones overlap, the one added last wins.
+    pub fn zone(mut self, zone: Zone) -> Self {
+        self.zones.push(zone);
+        self
+    }
+
+    /// Use the range, labels and zones of the given scale, replacing any zones added before
+    pub fn scale(mut self, scale: &Scale) -> Self {
+        self.min_value = scale.min_value;
+        self.max_value = scale.max_value;
+        self.labels = scale.labels;
+        self.zones = scale.zones.clone();
+        self
+    }
+
+    fn width(&self) -> f32 {
+        self.height / 3.0
+    }
+
+    fn tube_width(&self) -> f32 {
+        self.width() * 0.16
+    }
+
+    fn bulb_radius(&self) -> f32 {
+        self.tube_width()
+    }
+
+    fn label_font_size(&self) -> f32 {
+        self.height / 18.0
+    }
+
+    fn value_font_size(&self) -> f32 {
+        self.height / 12.0
+    }
+
+    fn tube_x(&self, rect: Rect) -> f32 {
+        rect.left() + self.width() * 0.35
+    }
+
+    fn bulb_center(&self, rect: Rect) -> Pos2 {
+        let text_space = if self.text.is_empty() {
+            self.label_font_size() / 2.0
+        } else {
+            self.label_font_size() * 1.5
+        };
+        Pos2 {
+            x: self.tube_x(rect),
+            y: rect.bottom() - text_space - self.bulb_radius(),
+        }
+    }
+
+    /// The top of the tube, which is where the maximum value is
+    fn scale_top(&self, rect: Rect) -> f32 {
+        rect.top() + self.value_font_size() * 1.6 + self.tube_width() / 2.0
+    }
+
+    /// Just above the bulb, which is where the minimum value is
+    fn scale_bottom(&self, rect: Rect) -> f32 {
+        self.bulb_center(rect).y - self.bulb_radius() * 1.5
+    }
+
+    fn value_to_y(&self, rect: Rect, v: f64) -> f32 {
+        let fraction = ((v - self.min_value) / (self.max_value - self.min_value)).clamp(0.0, 1.0);
+        let bottom = self.scale_bottom(rect);
+        bottom - (bottom - self.scale_top(rect)) * fraction as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        if !is_valid_range(self.min_value, self.max_value) {
+            self.paint_invalid(ui, rect, text_color.gamma_multiply(0.5));
+            return;
+        }
+        let color = zone::zone_at(&self.zones, self.value).map_or(self.color, |zone| zone.color);
+
+        self.paint_tube(ui, rect, color);
+        self.write_value(ui, rect, text_color);
+        self.write_graduations(ui, rect, text_color);
+
+        if !self.text.is_empty() {
+            self.write_text(ui, rect, text_color);
+        }
+    }
+
+    /// An empty, greyed out thermometer with a dash in place of the value, for ranges which
+    /// values can't be placed on
+    fn paint_invalid(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
+        let color = themed_track_color(ui).gamma_multiply(0.5);
+        ui.painter()
+            .rect_filled(self.tube_rect(rect), self.tube_width() / 2.0, color);
+        ui.painter()
+            .circle_filled(self.bulb_center(rect), self.bulb_radius(), color);
+        self.write_value_text(ui, rect, "—".to_owned(), text_color);
+        if !self.text.is_empty() {
+            self.write_text(ui, rect, text_color);
+        }
+    }
+
+    fn tube_rect(&self, rect: Rect) -> Rect {
+        let bulb = self.bulb_center(rect);
+        let x = self.tube_x(rect);
+        let half = self.tube_width() / 2.0;
+        Rect::from_min_max(
+            Pos2 {
+                x: x - half,
+                y: self.scale_top(rect) - half,
+            },
+            Pos2 {
+                x: x + half,
+                y: bulb.y,
+            },
+        )
+    }
+
+    fn paint_tube(&mut self, ui: &mut Ui, rect: Rect, color: Color32) {
+        let bulb = self.bulb_center(rect);
+        let rounding = self.tube_width() / 2.0;
+        let tube = self.tube_rect(rect);
+        ui.painter()
+            .rect_filled(tube, rounding, themed_track_color(ui));
+        ui.painter().circle_filled(bulb, self.bulb_radius(), color);
+
+        let mut liquid = tube;
+        liquid.min.y = self.value_to_y(rect, self.value);
+        ui.painter().rect_filled(liquid, rounding, color);
+    }
+
+    fn write_value(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
+        self.write_value_text(ui, rect, self.value.to_string(), text_color);
+    }
+
+    fn write_value_text(&mut self, ui: &mut Ui, rect: Rect, text: String, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: self.tube_x(rect),
+                y: rect.top() + self.value_font_size() * 0.6,
+            },
+            Align2::CENTER_CENTER,
+            text,
+            FontId {
+                size: self.value_font_size(),
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn write_text(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: self.tube_x(rect),
+                y: rect.bottom(),
+            },
+            Align2::CENTER_BOTTOM,
+            self.text.clone(),
+            FontId {
+                size: self.label_font_size(),
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn write_graduations(&mut self, ui: &mut Ui, rect: Rect, text_color: Color32) {
+        let tick_start = self.tube_x(rect) + self.tube_width() / 2.0 + self.width() * 0.04;
+        let tick_end = tick_start + self.width() * 0.1;
+        if self.labels.has_zero_tick(self.min_value, self.max_value) {
+            let y = self.value_to_y(rect, 0.0);
+            ui.painter().line_segment(
+                [
+                    Pos2 {
+                        x: self.tube_x(rect) - self.tube_width() / 2.0 - self.width() * 0.04,
+                        y,
+                    },
+                    Pos2 { x: tick_end, y },
+                ],
+                Stroke {
+                    width: 2.0,
+                    color: text_color,
+                },
+            );
+        }
+        for value in self.labels.values(self.min_value, self.max_value) {
+            let y = self.value_to_y(rect, value);
+            ui.painter().line_segment(
+                [Pos2 { x: tick_start, y }, Pos2 { x: tick_end, y }],
+                Stroke {
+                    width: 1.0,
+                    color: text_color,
+                },
+            );
+            ui.painter().text(
+                Pos2 {
+                    x: tick_end + self.width() * 0.04,
+                    y,
+                },
+                Align2::LEFT_CENTER,
+                self.labels.format(value, self.min_value, self.max_value),
+                FontId {
+                    size: self.label_font_size(),
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.width(), self.height);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            label: (!self.text.is_empty()).then(|| self.text.clone()),
+            value: Some(self.value),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for Thermometer {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
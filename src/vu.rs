@@ -0,0 +1,237 @@
+//! A level meter for audio, with the classic VU scale
+
+use crate::{arc_points, polar, themed_text_color, themed_track_color};
+use egui::{Align2, FontFamily, FontId, Id, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+const MIN_VU: f64 = -20.0;
+const MAX_VU: f64 = 3.0;
+const MARKS: [f64; 11] = [
+    -20.0, -10.0, -7.0, -5.0, -3.0, -2.0, -1.0, 0.0, 1.0, 2.0, 3.0,
+];
+
+/// The angle of the scale at the lowest and highest levels
+const START_ANGLE: f32 = 140.0;
+const END_ANGLE: f32 = 40.0;
+
+/// A meter showing an audio level in VU, on the conventional scale from -20 to +3 with the
+/// overload levels above 0 in red
+#[derive(Clone, Debug, PartialEq)]
+pub struct VuMeter {
+    level: f64,
+    width: f32,
+    color: Color32,
+    ballistics: Option<(f32, f32)>,
+}
+
+/// The level shown by a meter with ballistics, as of some point in time
+#[derive(Clone, Copy)]
+struct Needle {
+    level: f64,
+    time: f64,
+}
+
+impl VuMeter {
+    /// Create a meter showing the given level in VU. The given width is the width of the meter,
+    /// and its height is proportional to it. The given color is the color of the needle.
+    pub fn new(level: impl emath::Numeric, width: f32, color: Color32) -> Self {
+        Self {
+            level: level.to_f64(),
+            width,
+            color,
+            ballistics: None,
+        }
+    }
+
+    /// Move the needle like a physical meter instead of jumping to each new level. The needle
+    /// rises towards louder levels over `attack` seconds and falls back over `release` seconds, so
+    /// a fast attack and slow release like 0.01 and 0.3 make peaks easy to see.
+    pub fn ballistics(mut self, attack: f32, release: f32) -> Self {
+        self.ballistics = Some((attack, release));
+        self
+    }
+
+    fn height(&self) -> f32 {
+        self.width * 0.6
+    }
+
+    fn radius(&self) -> f32 {
+        self.width * 0.45
+    }
+
+    fn font_size(&self) -> f32 {
+        self.width / 20.0
+    }
+
+    fn pivot(&self, rect: Rect) -> Pos2 {
+        Pos2 {
+            x: rect.center().x,
+            y: rect.bottom() - self.width * 0.05,
+        }
+    }
+
+    /// The angle of the scale for the given level. The scale follows the voltage rather than the
+    /// level in decibels, which is what puts 0 VU about 70% of the way along it.
+    fn level_to_angle(level: f64) -> f32 {
+        let voltage = |level: f64| 10f64.powf(level.clamp(MIN_VU, MAX_VU) / 20.0);
+        let fraction = (voltage(level) - voltage(MIN_VU)) / (voltage(MAX_VU) - voltage(MIN_VU));
+        START_ANGLE + (END_ANGLE - START_ANGLE) * fraction as f32
+    }
+
+    /// The level to point the needle at, after applying the ballistics
+    fn needle_level(&self, ui: &Ui, id: Id) -> f64 {
+        let Some((attack, release)) = self.ballistics else {
+            return self.level;
+        };
+        let time = ui.input(|i| i.time);
+        let target = self.level.clamp(MIN_VU, MAX_VU);
+        let level = ui.data_mut(|d| {
+            let needle = d.get_temp_mut_or_insert_with(id, || Needle {
+                level: target,
+                time,
+            });
+            let dt = (time - needle.time).max(0.0);
+            let duration = if target > needle.level {
+                attack
+            } else {
+                release
+            };
+            let t = if duration > 0.0 {
+                1.0 - (-dt / duration as f64).exp()
+            } else {
+                1.0
+            };
+            needle.level += (target - needle.level) * t;
+            needle.time = time;
+            needle.level
+        });
+        if (target - level).abs() > 0.01 {
+            ui.ctx().request_repaint();
+        }
+        level
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect, needle_level: f64) {
+        let text_color = themed_text_color(ui);
+        let pivot = self.pivot(rect);
+
+        self.paint_scale(ui, pivot, text_color);
+        ui.painter().text(
+            Pos2 {
+                x: pivot.x,
+                y: pivot.y - self.radius() * 0.4,
+            },
+            Align2::CENTER_CENTER,
+            "VU",
+            FontId {
+                size: self.font_size() * 1.5,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        self.paint_needle(ui, pivot, needle_level);
+    }
+
+    fn paint_arc(&mut self, ui: &mut Ui, pivot: Pos2, from: f64, to: f64, stroke: Stroke) {
+        let from = Self::level_to_angle(from);
+        let to = Self::level_to_angle(to);
+        if to >= from {
+            return;
+        }
+        ui.painter().add(Shape::line(
+            arc_points(pivot, self.radius(), to, from),
+            stroke,
+        ));
+    }
+
+    fn paint_scale(&mut self, ui: &mut Ui, pivot: Pos2, text_color: Color32) {
+        let width = self.width / 100.0;
+        self.paint_arc(
+            ui,
+            pivot,
+            MIN_VU,
+            0.0,
+            Stroke {
+                width,
+                color: themed_track_color(ui),
+            },
+        );
+        self.paint_arc(
+            ui,
+            pivot,
+            0.0,
+            MAX_VU,
+            Stroke {
+                width: width * 3.0,
+                color: Color32::RED,
+            },
+        );
+
+        for level in MARKS {
+            let angle = Self::level_to_angle(level);
+            let color = if level > 0.0 {
+                Color32::RED
+            } else {
+                text_color
+            };
+            ui.painter().line_segment(
+                [
+                    polar(pivot, angle, self.radius()),
+                    polar(pivot, angle, self.radius() * 1.06),
+                ],
+                Stroke { width: 1.0, color },
+            );
+            let label = if level > 0.0 {
+                format!("+{level}")
+            } else {
+                format!("{level}")
+            };
+            ui.painter().text(
+                polar(pivot, angle, self.radius() * 1.14),
+                Align2::CENTER_CENTER,
+                label,
+                FontId {
+                    size: self.font_size(),
+                    family: FontFamily::Monospace,
+                },
+                color,
+            );
+        }
+    }
+
+    fn paint_needle(&mut self, ui: &mut Ui, pivot: Pos2, level: f64) {
+        let angle = Self::level_to_angle(level);
+        ui.painter().line_segment(
+            [pivot, polar(pivot, angle, self.radius() * 1.04)],
+            Stroke {
+                width: self.width / 150.0,
+                color: self.color,
+            },
+        );
+        ui.painter()
+            .circle_filled(pivot, self.width / 50.0, themed_track_color(ui));
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.width, self.height());
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            value: Some(self.level),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        let needle_level = self.needle_level(ui, response.id);
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect, needle_level);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for VuMeter {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,21 @@
+//! Gauges showing values sent on a `tokio::sync::watch` channel, from async tasks or other threads
+
+use egui::Context;
+use std::time::Duration;
+
+pub use tokio::sync::watch::{channel, Receiver, Sender};
+
+/// How often a gauge checks its channel for new values while nothing else repaints
+const POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Repaint the given `Context` right away if a new value was sent to the receiver, or else soon
+/// enough to check the channel again, until every sender is dropped. Nothing can wake egui up when
+/// a value is sent without a thread waiting on the channel, which isn't possible on the web.
+pub(crate) fn repaint_on_change(ctx: &Context, receiver: &Receiver<f64>) {
+    match receiver.has_changed() {
+        Ok(true) => ctx.request_repaint(),
+        Ok(false) => ctx.request_repaint_after(POLL_INTERVAL),
+        // no more values can be sent
+        Err(_) => {}
+    }
+}
@@ -0,0 +1,231 @@
+//! A dial for showing the direction and speed of the wind
+
+use crate::compass::{self, cardinal, heading_to_angle};
+use crate::{arc_points, polar, themed_text_color, themed_track_color, Dial};
+use egui::{Align2, FontFamily, FontId, Rect, Response, Sense, Shape, Ui};
+use epaint::{Color32, Pos2, Stroke};
+
+/// A weather instrument combining a compass ring, with an arrow for where the wind is coming from,
+/// and an arc for the wind speed with the speed written in the middle
+#[derive(Clone, Debug, PartialEq)]
+pub struct WindGauge {
+    direction: f64,
+    speed: f64,
+    max_speed: f64,
+    size: f32,
+    color: Color32,
+    gust: Option<f64>,
+    units: String,
+}
+
+impl WindGauge {
+    /// Create a wind gauge for wind coming from the given direction in degrees, with 0 being north
+    /// and increasing clockwise as on a compass, at the given speed on a scale from 0 to the given
+    /// maximum. Directions outside of 0 to 360 wrap around. The given size is the width and height
+    /// of the gauge. The given color is the color of the arrow and the speed arc.
+    pub fn new(
+        direction: impl emath::Numeric,
+        speed: impl emath::Numeric,
+        max_speed: impl emath::Numeric,
+        size: f32,
+        color: Color32,
+    ) -> Self {
+        Self {
+            direction: direction.to_f64().rem_euclid(360.0),
+            speed: speed.to_f64(),
+            max_speed: max_speed.to_f64(),
+            size,
+            color,
+            gust: None,
+            units: String::new(),
+        }
+    }
+
+    /// Mark the speed of the strongest recent gust on the speed arc
+    pub fn gust(mut self, speed: impl emath::Numeric) -> Self {
+        self.gust = Some(speed.to_f64());
+        self
+    }
+
+    /// Units to be displayed under the speed, like "kn" or "m/s"
+    pub fn units(mut self, units: impl Into<String>) -> Self {
+        self.units = units.into();
+        self
+    }
+
+    fn radius(&self) -> f32 {
+        self.size / 2.0 * 0.95
+    }
+
+    fn speed_radius(&self) -> f32 {
+        self.radius() * 0.6
+    }
+
+    fn speed_width(&self) -> f32 {
+        self.size / 25.0
+    }
+
+    fn font_size(&self) -> f32 {
+        self.size / 14.0
+    }
+
+    fn speed_to_angle(&self, speed: f64) -> f32 {
+        let dial = Dial::ThreeQuarter;
+        let fraction = if self.max_speed > 0.0 {
+            (speed / self.max_speed).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        dial.start_angle() as f32 - dial.sweep() as f32 * fraction as f32
+    }
+
+    fn paint(&mut self, ui: &mut Ui, rect: Rect) {
+        let text_color = themed_text_color(ui);
+        let center = rect.center();
+
+        ui.painter().circle_stroke(
+            center,
+            self.radius(),
+            Stroke {
+                width: self.size / 60.0,
+                color: themed_track_color(ui),
+            },
+        );
+        compass::paint_ticks(ui.painter(), center, self.radius(), text_color);
+        compass::write_cardinals(
+            ui.painter(),
+            center,
+            self.radius(),
+            self.font_size(),
+            text_color,
+        );
+
+        self.paint_speed_arc(ui, center, self.max_speed, themed_track_color(ui));
+        self.paint_speed_arc(ui, center, self.speed, self.color);
+        if let Some(gust) = self.gust {
+            self.paint_gust(ui, center, gust, text_color);
+        }
+        self.paint_arrow(ui, center);
+        self.write_speed(ui, center, text_color);
+        self.write_direction(ui, center, text_color);
+    }
+
+    /// An arc from no wind up to the given speed
+    fn paint_speed_arc(&mut self, ui: &mut Ui, center: Pos2, speed: f64, color: Color32) {
+        let from = self.speed_to_angle(0.0);
+        let to = self.speed_to_angle(speed);
+        if to >= from {
+            return;
+        }
+        ui.painter().add(Shape::line(
+            arc_points(center, self.speed_radius(), to, from),
+            Stroke {
+                width: self.speed_width(),
+                color,
+            },
+        ));
+    }
+
+    fn paint_gust(&mut self, ui: &mut Ui, center: Pos2, gust: f64, text_color: Color32) {
+        let angle = self.speed_to_angle(gust);
+        let half_width = self.speed_width() * 0.8;
+        ui.painter().line_segment(
+            [
+                polar(center, angle, self.speed_radius() - half_width),
+                polar(center, angle, self.speed_radius() + half_width),
+            ],
+            Stroke {
+                width: self.size / 100.0,
+                color: text_color,
+            },
+        );
+    }
+
+    /// An arrow on the ring at the direction the wind is coming from, pointing the way it blows
+    fn paint_arrow(&mut self, ui: &mut Ui, center: Pos2) {
+        let angle = heading_to_angle(self.direction);
+        let tip = self.radius() * 0.86;
+        let base = self.radius() * 1.02;
+        ui.painter().add(Shape::convex_polygon(
+            vec![
+                polar(center, angle, tip),
+                polar(center, angle - 6.0, base),
+                polar(center, angle + 6.0, base),
+            ],
+            self.color,
+            Stroke::NONE,
+        ));
+    }
+
+    fn write_speed(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        ui.painter().text(
+            center,
+            Align2::CENTER_CENTER,
+            format!("{:.0}", self.speed),
+            FontId {
+                size: self.size / 8.0,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+        if !self.units.is_empty() {
+            ui.painter().text(
+                Pos2 {
+                    x: center.x,
+                    y: center.y + self.radius() * 0.2,
+                },
+                Align2::CENTER_CENTER,
+                self.units.clone(),
+                FontId {
+                    size: self.font_size() * 0.8,
+                    family: FontFamily::Monospace,
+                },
+                text_color,
+            );
+        }
+    }
+
+    /// The direction in the opening at the bottom of the speed arc
+    fn write_direction(&mut self, ui: &mut Ui, center: Pos2, text_color: Color32) {
+        ui.painter().text(
+            Pos2 {
+                x: center.x,
+                y: center.y + self.radius() * 0.46,
+            },
+            Align2::CENTER_CENTER,
+            format!("{} {:.0}°", cardinal(self.direction), self.direction),
+            FontId {
+                size: self.font_size() * 0.8,
+                family: FontFamily::Monospace,
+            },
+            text_color,
+        );
+    }
+
+    fn add_contents(&mut self, ui: &mut Ui) -> Response {
+        let desired_size = egui::vec2(self.size, self.size);
+        let (rect, response) = ui.allocate_exact_size(desired_size, Sense::hover());
+
+        response.widget_info(|| egui::WidgetInfo {
+            label: Some(format!(
+                "wind from {} {:.0}°",
+                cardinal(self.direction),
+                self.direction
+            )),
+            value: Some(self.speed),
+            ..egui::WidgetInfo::new(egui::WidgetType::ProgressIndicator)
+        });
+
+        if ui.is_rect_visible(rect) {
+            self.paint(ui, rect);
+        }
+
+        response
+    }
+}
+
+impl egui::Widget for WindGauge {
+    fn ui(mut self, ui: &mut Ui) -> Response {
+        self.add_contents(ui)
+    }
+}
@@ -0,0 +1,157 @@
+//! Ranges of values on a gauge which have their own meaning, like a warning zone
+
+use epaint::Color32;
+use std::ops::RangeInclusive;
+
+/// How serious it is for the value of a gauge to be inside of a zone
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Severity {
+    /// Nothing to be alarmed about
+    #[default]
+    Normal,
+    /// The value deserves attention
+    Warning,
+    /// The value needs immediate attention
+    Critical,
+}
+
+/// A range of the values on a gauge which is colored differently and can raise an alarm. Zones
+/// are shown as bands along the outside of the arc of a [`Gauge`](crate::Gauge), and their names
+/// are used in its tooltip and accessibility descriptions.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Zone {
+    /// The values covered by the zone
+    pub range: RangeInclusive<f64>,
+    /// The color used for the arc while the value is inside the zone
+    pub color: Color32,
+    /// How serious it is for the value to be inside the zone
+    pub severity: Severity,
+    /// What the zone means, like "overheating", or empty for a zone without a name
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub name: String,
+    /// The color of the value in the center of a [`Gauge`](crate::Gauge) while the value is
+    /// inside the zone
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub text_color: Option<Color32>,
+    /// How far past its ends the value has to go to leave the zone once it is inside of it
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub hysteresis: f64,
+}
+
+impl Zone {
+    /// Create a zone covering the given range. When the value is inside of it, the gauge uses the
+    /// given color in place of its own.
+    pub fn new<Num: emath::Numeric>(range: RangeInclusive<Num>, color: Color32) -> Self {
+        Self {
+            range: range.start().to_f64()..=range.end().to_f64(),
+            color,
+            severity: Severity::Normal,
+            name: String::new(),
+            text_color: None,
+            hysteresis: 0.0,
+        }
+    }
+
+    /// Give the zone a name, like "overheating", which is shown when the value is inside of it
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
+        self
+    }
+
+    /// Make this a zone for values which deserve attention
+    pub fn warning(mut self) -> Self {
+        self.severity = Severity::Warning;
+        self
+    }
+
+    /// Make this a zone for values which need immediate attention
+    pub fn critical(mut self) -> Self {
+        self.severity = Severity::Critical;
+        self
+    }
+
+    /// Show the value in the center of the gauge in the given color while it is inside the zone.
+    /// See also [`Gauge::zone_text_color`](crate::Gauge::zone_text_color).
+    pub fn text_color(mut self, color: Color32) -> Self {
+        self.text_color = Some(color);
+        self
+    }
+
+    /// Only leave the zone once the value is the given distance past its ends, so that a value
+    /// hovering around the boundary doesn't flicker in and out of the zone. For example, a zone
+    /// from 90 with a hysteresis of 5 is entered above 90 but only left again below 85.
+    pub fn hysteresis(mut self, hysteresis: impl emath::Numeric) -> Self {
+        self.hysteresis = hysteresis.to_f64().max(0.0);
+        self
+    }
+
+    /// Whether a value inside of this zone should raise an alarm
+    pub fn is_alarm(&self) -> bool {
+        self.severity > Severity::Normal
+    }
+
+    pub(crate) fn contains(&self, value: f64) -> bool {
+        self.range.contains(&value)
+    }
+
+    /// What the zone is called in its tooltip: its name, like "Overheating (critical)", or how
+    /// serious it is, like "Warning", for zones without a name
+    pub(crate) fn title(&self) -> String {
+        let severity = match self.severity {
+            Severity::Normal => "",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        };
+        if self.name.is_empty() {
+            let mut chars = severity.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => "Zone".into(),
+            }
+        } else if severity.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({severity})", self.name)
+        }
+    }
+
+    /// How the zone is described to the user, like "overheating zone, critical"
+    pub(crate) fn description(&self) -> String {
+        let severity = match self.severity {
+            Severity::Normal => "",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        };
+        match (self.name.is_empty(), severity.is_empty()) {
+            (true, true) => String::new(),
+            (true, false) => format!("{severity} zone"),
+            (false, true) => format!("{} zone", self.name),
+            (false, false) => format!("{} zone, {severity}", self.name),
+        }
+    }
+}
+
+/// The zone the given value is inside of. When zones overlap, the last one wins.
+pub(crate) fn zone_at(zones: &[Zone], value: f64) -> Option<&Zone> {
+    zones.iter().rev().find(|zone| zone.contains(value))
+}
+
+/// The index of the zone the given value is inside of, when it was inside of the zone at the
+/// given index before. The value stays in the zone it was in while it is within its hysteresis,
+/// unless it enters a zone which wins over it.
+pub(crate) fn zone_index_at(zones: &[Zone], value: f64, previous: Option<usize>) -> Option<usize> {
+    let entered = zones.iter().rposition(|zone| zone.contains(value));
+    let kept = previous.filter(|&i| {
+        zones.get(i).is_some_and(|zone| {
+            let range = zone.range.start() - zone.hysteresis..=zone.range.end() + zone.hysteresis;
+            range.contains(&value)
+        })
+    });
+    match (kept, entered) {
+        (Some(kept), Some(entered)) if entered > kept => Some(entered),
+        (Some(kept), _) => Some(kept),
+        (None, entered) => entered,
+    }
+}
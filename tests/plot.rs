@@ -0,0 +1,54 @@
+//! Gauges shown as items of `egui_plot` plots
+#![cfg(feature = "egui_plot")]
+
+use egui::{CentralPanel, Context, RawInput, Rect, Shape};
+use egui_gauge::{Gauge, PlotGauge};
+use egui_plot::{Corner, Plot};
+use epaint::Color32;
+
+const COLOR: Color32 = Color32::from_rgb(1, 2, 3);
+
+/// The bounds of the arcs painted in the gauge's color, and the rect of the plot
+fn show(gauge: impl FnOnce(Gauge<'static>) -> PlotGauge) -> (Rect, Rect) {
+    let ctx = Context::default();
+    let mut plot_rect = Rect::NOTHING;
+    let output = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let response = Plot::new("plot")
+                .include_x(0.0)
+                .include_x(10.0)
+                .include_y(0.0)
+                .include_y(10.0)
+                .show(ui, |plot_ui| {
+                    plot_ui.add(gauge(Gauge::new(100, 0..=100, 60.0, COLOR)));
+                });
+            plot_rect = response.response.rect;
+        });
+    });
+    let mut bounds = Rect::NOTHING;
+    for clipped in output.shapes {
+        if let Shape::Path(path) = clipped.shape {
+            if path.stroke.color == COLOR {
+                bounds = bounds.union(path.visual_bounding_rect());
+            }
+        }
+    }
+    (bounds, plot_rect)
+}
+
+#[test]
+fn gauge_in_a_corner_is_painted_in_the_corner() {
+    let (bounds, plot) = show(|gauge| PlotGauge::in_corner(gauge, Corner::RightTop));
+    assert!(bounds.is_positive());
+    assert!(plot.contains_rect(bounds));
+    assert!(bounds.center().x > plot.center().x);
+    assert!(bounds.center().y < plot.center().y);
+}
+
+#[test]
+fn gauge_on_a_point_is_painted_around_the_point() {
+    let (bounds, plot) = show(|gauge| PlotGauge::new(gauge, [0.0, 0.0]));
+    assert!(bounds.is_positive());
+    assert!(bounds.center().x < plot.center().x);
+    assert!(bounds.center().y > plot.center().y);
+}
@@ -0,0 +1,118 @@
+//! Snapshot tests of gauges rendered without a window
+#![cfg(feature = "render")]
+
+use egui::{ColorImage, Visuals};
+use egui_gauge::{Dial, Gauge, GaugeStyle, Palette, Zone};
+use epaint::Color32;
+
+/// An FNV-1a hash of the pixels, which changes whenever the rendering does
+fn snapshot(image: &ColorImage) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325u64;
+    for byte in image.pixels.iter().flat_map(|pixel| pixel.to_array()) {
+        hash = (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+fn pixel(image: &ColorImage, x: usize, y: usize) -> Color32 {
+    image.pixels[y * image.size[0] + x]
+}
+
+fn gauge(value: f64) -> Gauge<'static> {
+    Gauge::new(value, 0.0..=100.0, 200.0, Color32::BLUE).text("speed")
+}
+
+#[test]
+fn rendering_is_deterministic() {
+    let visuals = Visuals::light();
+    assert_eq!(
+        snapshot(&gauge(42.0).render_to_image(&visuals, 1.0)),
+        snapshot(&gauge(42.0).render_to_image(&visuals, 1.0))
+    );
+}
+
+#[test]
+fn image_is_the_desired_size_in_pixels() {
+    let image = gauge(42.0).render_to_image(&Visuals::light(), 2.0);
+    let size = gauge(42.0).desired_size() * 2.0;
+    assert_eq!(image.size, [size.x.ceil() as usize, size.y.ceil() as usize]);
+}
+
+#[test]
+fn value_arc_is_painted_in_the_color_of_the_gauge() {
+    let visuals = Visuals::light();
+    // the middle of the arc at the top of the dial, which is halfway along the range
+    let (x, y) = (100, 25);
+    assert_eq!(
+        pixel(&gauge(100.0).render_to_image(&visuals, 1.0), x, y),
+        Color32::BLUE
+    );
+    assert_ne!(
+        pixel(&gauge(0.0).render_to_image(&visuals, 1.0), x, y),
+        Color32::BLUE
+    );
+}
+
+#[test]
+fn snapshots() {
+    let cases = [
+        ("default", gauge(42.0), Visuals::light()),
+        ("dark", gauge(42.0), Visuals::dark()),
+        (
+            "zones",
+            gauge(85.0)
+                .zone(Zone::new(60..=80, Color32::YELLOW).warning())
+                .zone(Zone::new(80..=100, Color32::RED).critical()),
+            Visuals::light(),
+        ),
+        ("half", gauge(42.0).dial(Dial::Half), Visuals::light()),
+        (
+            "high contrast",
+            gauge(42.0).style(GaugeStyle {
+                high_contrast: Some(true),
+                palette: Some(Palette::OKABE_ITO),
+                ..Default::default()
+            }),
+            Visuals::light(),
+        ),
+    ];
+    let snapshots: Vec<_> = cases
+        .iter()
+        .map(|(name, gauge, visuals)| {
+            let image = gauge.render_to_image(visuals, 1.0);
+            let hash = snapshot(&image);
+            if EXPECTED.iter().all(|expected| expected != &(*name, hash)) {
+                write_ppm(name, &image);
+            }
+            (*name, hash)
+        })
+        .collect();
+    assert_eq!(
+        snapshots,
+        EXPECTED,
+        "images of the changed snapshots are in {}",
+        env!("CARGO_TARGET_TMPDIR")
+    );
+}
+
+/// Write the image as a PPM file, which most image viewers open, for looking at what changed
+fn write_ppm(name: &str, image: &ColorImage) {
+    let [width, height] = image.size;
+    let mut ppm = format!("P6 {width} {height} 255\n").into_bytes();
+    for pixel in &image.pixels {
+        // over white, since the images have a transparent background
+        let [r, g, b, a] = pixel.to_array();
+        ppm.extend([r, g, b].map(|c| c.saturating_add(255 - a)));
+    }
+    let path = std::path::Path::new(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.ppm"));
+    std::fs::write(path, ppm).unwrap();
+}
+
+/// Update these when a change to the rendering is intended, after checking the new images
+const EXPECTED: [(&str, u64); 5] = [
+    ("default", 9991690923350405787),
+    ("dark", 16762796036341230987),
+    ("zones", 12674108730283196743),
+    ("half", 4199967625088752124),
+    ("high contrast", 6063927535703485101),
+];
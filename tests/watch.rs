@@ -0,0 +1,71 @@
+//! Gauges reading values from a `tokio::sync::watch` channel
+#![cfg(feature = "watch")]
+
+use egui::{CentralPanel, Context, RawInput, ViewportId};
+use egui_gauge::{watch, Gauge, GaugeOutput};
+use epaint::Color32;
+use std::time::Duration;
+
+/// Show the gauge for one frame, along with how long egui may wait before the next one
+fn show(ctx: &Context, receiver: &mut watch::Receiver<f64>) -> (GaugeOutput, Duration) {
+    let mut output = None;
+    let full_output = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            let gauge = Gauge::from_watch(receiver, 0.0..=100.0, 100.0, Color32::BLUE);
+            output = Some(gauge.show(ui).inner);
+        });
+    });
+    let delay = full_output.viewport_output[&ViewportId::ROOT].repaint_delay;
+    (output.unwrap(), delay)
+}
+
+#[test]
+fn the_channel_is_checked_again_soon_while_values_can_be_sent() {
+    let ctx = Context::default();
+    let (sender, mut receiver) = watch::channel(10.0);
+    assert_eq!(show(&ctx, &mut receiver).0.value, 10.0);
+    // let the repaints egui asks for after the first frames run out
+    for _ in 0..10 {
+        let (_, delay) = show(&ctx, &mut receiver);
+        assert!(
+            delay <= Duration::from_secs(1),
+            "not repainted in {delay:?}"
+        );
+    }
+
+    sender.send(20.0).unwrap();
+    assert_eq!(show(&ctx, &mut receiver).0.value, 20.0);
+}
+
+#[test]
+fn the_channel_is_not_checked_once_the_senders_are_gone() {
+    let ctx = Context::default();
+    let (sender, mut receiver) = watch::channel(10.0);
+    drop(sender);
+    let mut delay = Duration::ZERO;
+    for _ in 0..10 {
+        let (output, frame_delay) = show(&ctx, &mut receiver);
+        assert_eq!(output.value, 10.0);
+        delay = frame_delay;
+    }
+    assert_eq!(delay, Duration::MAX);
+}
+
+#[test]
+fn a_range_of_integers_keeps_the_gauge_to_whole_numbers() {
+    let ctx = Context::default();
+    let (_sender, mut receiver) = watch::channel(42.4);
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        let gauge = Gauge::from_watch(&mut receiver, 0..=100, 100.0, Color32::BLUE);
+        let rect = egui::Rect::from_min_size(Default::default(), gauge.desired_size());
+        let texts: Vec<_> = gauge
+            .shapes(ctx, rect)
+            .into_iter()
+            .filter_map(|shape| match shape {
+                egui::Shape::Text(text) => Some(text.galley.job.text.clone()),
+                _ => None,
+            })
+            .collect();
+        assert!(texts.contains(&"42".to_owned()), "{texts:?}");
+    });
+}
@@ -0,0 +1,175 @@
+//! Shapes painted by the widgets, and how they line up with what the gauge reports about itself
+
+use egui::{CentralPanel, Context, Pos2, RawInput, Rect, Shape, Widget};
+use egui_gauge::{AtomicF64, BulletGraph, Donut, Gauge, GaugeStyle, GaugeTheme, Tachometer};
+use epaint::Color32;
+use std::ops::RangeInclusive;
+
+const COLOR: Color32 = Color32::from_rgb(1, 2, 3);
+
+/// Every shape painted when showing the widget for a frame
+fn shapes(widget: impl Widget) -> Vec<Shape> {
+    let ctx = Context::default();
+    let output = ctx.run(RawInput::default(), |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            ui.add(widget);
+        });
+    });
+    output
+        .shapes
+        .into_iter()
+        .map(|clipped| clipped.shape)
+        .collect()
+}
+
+/// The rects filled with the given color
+fn filled(shapes: &[Shape], color: Color32) -> Vec<Rect> {
+    shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::Rect(rect) if rect.fill == color => Some(rect.rect),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn bullet_graph_with_a_reversed_range_is_painted_like_the_normal_one() {
+    let normal = filled(&shapes(BulletGraph::new(40, 0..=100, 300.0, COLOR)), COLOR);
+    let reversed = filled(
+        &shapes(BulletGraph::new(
+            40,
+            RangeInclusive::new(100, 0),
+            300.0,
+            COLOR,
+        )),
+        COLOR,
+    );
+    assert_eq!(normal.len(), 1);
+    assert_eq!(normal, reversed);
+}
+
+#[test]
+fn bullet_graph_with_an_empty_range_paints_no_bar() {
+    let shapes = shapes(
+        BulletGraph::new(50, 50..=50, 300.0, COLOR)
+            .band(0..=50, Color32::GRAY)
+            .target(50),
+    );
+    assert!(filled(&shapes, COLOR).is_empty());
+    for shape in &shapes {
+        let bounds = shape.visual_bounding_rect();
+        assert!(
+            bounds.min.is_finite() && bounds.max.is_finite(),
+            "{shape:?} isn't placed anywhere"
+        );
+    }
+}
+
+#[test]
+fn donut_total_is_written_without_float_artifacts() {
+    let shapes = shapes(
+        Donut::new(150.0)
+            .segment("a", 1.1, COLOR)
+            .segment("b", 2.2, COLOR),
+    );
+    let texts: Vec<_> = shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::Text(text) => Some(text.galley.job.text.as_str()),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(texts, ["3.3"]);
+}
+
+#[test]
+fn tachometer_with_a_huge_range_is_labelled_a_few_thousands_apart() {
+    let shapes = shapes(Tachometer::new(0, 1e9, 200.0, COLOR));
+    let labels: Vec<_> = shapes
+        .iter()
+        .filter_map(|shape| match shape {
+            Shape::Text(text) => text.galley.job.text.parse::<u64>().ok(),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(labels.first(), Some(&0));
+    assert!(labels.len() <= 13, "{} labels", labels.len());
+    assert!(shapes.len() < 100, "{} shapes", shapes.len());
+}
+
+/// Whether the gauge writes the given text anywhere
+fn writes(gauge: Gauge<'_>, text: &str) -> bool {
+    fn find(shapes: &[Shape], text: &str) -> bool {
+        shapes.iter().any(|shape| match shape {
+            Shape::Vec(shapes) => find(shapes, text),
+            Shape::Text(shape) => shape.galley.job.text == text,
+            _ => false,
+        })
+    }
+    let ctx = Context::default();
+    let mut found = false;
+    let _ = ctx.run(RawInput::default(), |ctx| {
+        let rect = Rect::from_min_size(Default::default(), gauge.desired_size());
+        found = find(&gauge.shapes(ctx, rect), text);
+    });
+    found
+}
+
+#[test]
+fn gauges_of_integers_write_whole_numbers_whatever_the_source() {
+    let atomic = AtomicF64::new(42.4);
+    assert!(writes(
+        Gauge::from_atomic(&atomic, 0.0..=100.0, 200.0, COLOR),
+        "42.4"
+    ));
+    assert!(writes(
+        Gauge::from_atomic(&atomic, 0..=100, 200.0, COLOR),
+        "42"
+    ));
+    assert!(writes(
+        Gauge::from_get_set(0..=100, 200.0, COLOR, |_| 42.4),
+        "42"
+    ));
+}
+
+#[test]
+fn geometry_of_a_fitted_and_themed_gauge_matches_the_painted_arc() {
+    let ctx = Context::default();
+    let style = GaugeStyle {
+        thickness: Some(0.2),
+        ..Default::default()
+    };
+    GaugeTheme::install(&ctx, GaugeTheme { gauge: style });
+    let mut gauge = Gauge::new(100, 0..=100, 100.0, COLOR).fit_to_available_size();
+    let input = RawInput {
+        screen_rect: Some(Rect::from_min_size(Pos2::ZERO, egui::vec2(400.0, 300.0))),
+        ..Default::default()
+    };
+    let mut rect = Rect::NOTHING;
+    let output = ctx.run(input, |ctx| {
+        CentralPanel::default().show(ctx, |ui| {
+            rect = ui.add(&mut gauge).rect;
+        });
+    });
+
+    let arc = output
+        .shapes
+        .iter()
+        .find_map(|clipped| match &clipped.shape {
+            Shape::Path(path) if path.stroke.color == COLOR => Some(path),
+            _ => None,
+        })
+        .expect("the value arc is painted");
+    assert!((arc.stroke.width - gauge.arc_thickness()).abs() < 0.01);
+    let middle = gauge.screen_pos(rect, 100, gauge.dial_radius() - gauge.arc_thickness() / 2.0);
+    let end = *arc
+        .points
+        .iter()
+        .min_by(|a, b| a.distance(middle).total_cmp(&b.distance(middle)))
+        .unwrap();
+    assert!(end.distance(middle) < 0.5, "{end:?} isn't near {middle:?}");
+    assert!([arc.points.first(), arc.points.last()].contains(&Some(&end)));
+    let outside = gauge.screen_pos(rect, 100, gauge.dial_radius());
+    assert!((outside.distance(end) - gauge.arc_thickness() / 2.0).abs() < 0.5);
+}